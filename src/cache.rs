@@ -0,0 +1,64 @@
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// response bodies cached by path until they are older than the ttl, clones share the same entries
+#[derive(Clone, Debug)]
+pub(crate) struct Cache {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<String, (Instant, Bytes)>>>,
+}
+
+impl Cache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    // the cached body of path, `None` if it isn't cached or has expired
+    pub(crate) fn get(&self, path: &str) -> Option<Bytes> {
+        let entries = self.entries.lock().unwrap();
+        match entries.get(path) {
+            Some((cached_at, body)) if cached_at.elapsed() < self.ttl => Some(body.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, path: &str, body: Bytes) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), (Instant::now(), body));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_expires() {
+        let cache = Cache::new(Duration::from_millis(50));
+        cache.insert("/accounts", Bytes::from_static(b"[]"));
+        assert_eq!(cache.get("/accounts"), Some(Bytes::from_static(b"[]")));
+        assert_eq!(cache.get("/currencies"), None);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(cache.get("/accounts"), None);
+    }
+
+    #[test]
+    fn test_clear_shared_by_clones() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.insert("/accounts", Bytes::from_static(b"[]"));
+        cache.clone().clear();
+        assert_eq!(cache.get("/accounts"), None);
+    }
+}
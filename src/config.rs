@@ -0,0 +1,105 @@
+#[cfg(feature = "full")]
+use crate::cache::Cache;
+use crate::error::{Error, ErrorKind};
+use crate::rate_limit::RateLimiter;
+use std::time::Duration;
+
+// http configuration shared by the `PublicClient` and `PrivateClient` builders
+#[derive(Clone, Debug)]
+pub(crate) struct ClientConfig {
+    pub(crate) pool_idle_timeout: Option<Duration>,
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+    // overrides the default user agent of the client
+    pub(crate) user_agent: Option<String>,
+    // accept gzip and brotli compressed responses
+    pub(crate) compression: bool,
+    pub(crate) proxies: Vec<reqwest::Proxy>,
+    // use the proxies of the `HTTP_PROXY` and `HTTPS_PROXY` environment variables, off unless opted in
+    pub(crate) env_proxy: bool,
+    // requests per second and burst
+    pub(crate) rate_limit: Option<(u32, u32)>,
+    #[cfg(feature = "full")]
+    pub(crate) cache_ttl: Option<Duration>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            // reqwest's default
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            pool_max_idle_per_host: None,
+            user_agent: None,
+            compression: true,
+            proxies: Vec::new(),
+            env_proxy: false,
+            rate_limit: None,
+            #[cfg(feature = "full")]
+            cache_ttl: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    // builds the reqwest client, options which aren't set keep reqwest's defaults
+    pub(crate) fn build_reqwest_client(&self) -> Result<reqwest::Client, Error> {
+        let mut builder = reqwest::Client::builder().pool_idle_timeout(self.pool_idle_timeout);
+        if let Some(n) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(n);
+        }
+        if !self.compression {
+            builder = builder.no_gzip().no_brotli();
+        }
+        if !self.env_proxy {
+            builder = builder.no_proxy();
+        }
+        for proxy in &self.proxies {
+            builder = builder.proxy(proxy.clone());
+        }
+        Ok(builder.build()?)
+    }
+
+    // the reqwest client of a client created without a builder
+    pub(crate) fn default_reqwest_client() -> reqwest::Client {
+        Self::default()
+            .build_reqwest_client()
+            .expect("failed to build the http client")
+    }
+
+    // the user agent sent with each request, `default` unless the user agent is overridden
+    pub(crate) fn user_agent(
+        &self,
+        default: &'static str,
+    ) -> Result<reqwest::header::HeaderValue, Error> {
+        match self.user_agent {
+            Some(ref n) => reqwest::header::HeaderValue::from_str(n).map_err(|_| {
+                Error::new(ErrorKind::InvalidParameter(
+                    "user agent contains characters which are not allowed in a http header"
+                        .to_string(),
+                ))
+            }),
+            None => Ok(reqwest::header::HeaderValue::from_static(default)),
+        }
+    }
+
+    // the rate limiter of the client, `None` unless a rate limit is set
+    pub(crate) fn rate_limiter(&self) -> Result<Option<RateLimiter>, Error> {
+        match self.rate_limit {
+            Some((requests_per_second, burst)) if requests_per_second == 0 || burst == 0 => {
+                Err(Error::new(ErrorKind::InvalidParameter(
+                    "rate limit requests per second and burst must be greater than zero"
+                        .to_string(),
+                )))
+            }
+            Some((requests_per_second, burst)) => {
+                Ok(Some(RateLimiter::new(requests_per_second, burst)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // the response cache of the client, `None` unless a cache ttl is set
+    #[cfg(feature = "full")]
+    pub(crate) fn cache(&self) -> Option<Cache> {
+        self.cache_ttl.map(Cache::new)
+    }
+}
@@ -2,6 +2,11 @@ use serde::Deserialize;
 use serde_json;
 use std::error::Error as StdError;
 use std::fmt;
+use std::time::SystemTimeError;
+
+/// The error type of the crate
+/// <br>
+/// `Error` is `Send + Sync + 'static` so it can be moved across `.await` points and task boundaries
 #[derive(Debug)]
 pub struct Error {
     pub kind: ErrorKind,
@@ -15,12 +20,62 @@ impl fmt::Display for Error {
             ErrorKind::HTTP(_) => {
                 write!(f, "http error")
             }
+            ErrorKind::Status(err) if err.code == 401 => {
+                write!(
+                    f,
+                    "status code: {}, message: {} (check that the system clock is correct, or call `PrivateClient::sync_time`, and the secret, passphrase and key are those of the API key)",
+                    err.code, err.message
+                )
+            }
+            ErrorKind::Status(err) if !err.details.is_empty() => {
+                write!(
+                    f,
+                    "status code: {}, message: {}, details: {}",
+                    err.code,
+                    err.message,
+                    serde_json::Value::Object(err.details.clone())
+                )
+            }
             ErrorKind::Status(err) => {
                 write!(f, "status code: {}, message: {}", err.code, err.message)
             }
             ErrorKind::JSON(_) => {
                 write!(f, "json error")
             }
+            ErrorKind::Time(_) => {
+                write!(f, "system time error")
+            }
+            ErrorKind::InvalidParameter(message) => {
+                write!(f, "invalid parameter: {}", message)
+            }
+            ErrorKind::Timeout => {
+                write!(f, "timed out")
+            }
+            ErrorKind::ReportNotReady(status) => {
+                write!(f, "report is not ready, status: {}", status)
+            }
+            ErrorKind::EmptyOrderBook => {
+                write!(f, "order book has no bids or no asks")
+            }
+            ErrorKind::TooManyPages(max_pages) => {
+                write!(f, "more than the maximum of {} pages", max_pages)
+            }
+            #[cfg(feature = "websocket")]
+            ErrorKind::WebSocket(_) => {
+                write!(f, "websocket error")
+            }
+            #[cfg(feature = "websocket")]
+            ErrorKind::Feed(message) => {
+                write!(f, "websocket feed error: {}", message)
+            }
+            #[cfg(feature = "websocket")]
+            ErrorKind::SequenceGap { expected, received } => {
+                write!(
+                    f,
+                    "sequence gap, expected: {}, received: {}",
+                    expected, received
+                )
+            }
         }
     }
 }
@@ -41,31 +96,108 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "websocket")]
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self {
+            kind: ErrorKind::WebSocket(Box::new(e)),
+        }
+    }
+}
+
+impl From<SystemTimeError> for Error {
+    fn from(e: SystemTimeError) -> Self {
+        Self {
+            kind: ErrorKind::Time(e),
+        }
+    }
+}
+
 impl Error {
     pub fn new(kind: ErrorKind) -> Self {
         Self { kind }
     }
+
+    /// Returns the kind of the error
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Consumes the error, returning its kind
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns `true` if coinbase rejected the request with a 401 or 403 status
+    /// <br>
+    /// A 401 usually means the request was signed incorrectly or the system clock is wrong, a 403 that the API key lacks the permission the request requires
+    pub fn is_auth_error(&self) -> bool {
+        matches!(&self.kind, ErrorKind::Status(err) if err.code == 401 || err.code == 403)
+    }
+
+    /// Returns the http status code of a `Status` error, or of an `HTTP` error which has one
+    pub fn status_code(&self) -> Option<u16> {
+        match &self.kind {
+            ErrorKind::Status(err) => Some(err.code),
+            ErrorKind::HTTP(err) => err.status().map(|n| n.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if coinbase rejected the request with a 429 status because the rate limit was exceeded
+    pub fn is_rate_limited(&self) -> bool {
+        self.status_code() == Some(429)
+    }
 }
 
+/// The kind of an `Error`
+/// <br>
+/// New kinds may be added in future releases, so matches on `ErrorKind` need a wildcard arm
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
     HTTP(reqwest::Error),
     Status(StatusError),
     JSON(serde_json::Error),
+    /// The system clock is set before the unix epoch
+    Time(SystemTimeError),
+    /// A parameter was rejected before sending a request, holds a description of the problem
+    InvalidParameter(String),
+    /// A polling operation did not complete before its timeout
+    Timeout,
+    /// A report was downloaded before its file was generated, holds the status of the report
+    ReportNotReady(String),
+    /// The order book of a product has no bids or no asks, so there is no best bid or ask
+    EmptyOrderBook,
+    /// A paginated request had more pages than it was allowed to request, holds the maximum number of pages
+    TooManyPages(usize),
+    #[cfg(feature = "websocket")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    /// The websocket feed sent an error message, e.g. for an invalid subscription, holds the message
+    #[cfg(feature = "websocket")]
+    Feed(String),
+    /// A message of the websocket feed was missed, the order book needs a new snapshot
+    #[cfg(feature = "websocket")]
+    SequenceGap { expected: u64, received: u64 },
 }
 
 #[derive(Debug)]
 pub struct StatusError {
     pub code: u16,
     pub message: String,
+    /// Fields of the error body other than `message`, which some validation errors include to describe the problem, empty for most errors
+    pub details: serde_json::Map<String, serde_json::Value>
 }
-
 impl StatusError {
     pub fn new(code: u16, message: String) -> Self {
-        Self { code, message }
+        Self { code, message, details: serde_json::Map::new() }
     }
 }
+/// The body of an error response, usually only a `message`
 #[derive(Deserialize)]
 pub struct ErrorMessage {
-    pub message: String
+    #[serde(default)]
+    pub message: String,
+    #[serde(flatten)]
+    pub details: serde_json::Map<String, serde_json::Value>
 }
\ No newline at end of file
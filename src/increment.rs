@@ -0,0 +1,174 @@
+use crate::error::{Error, ErrorKind};
+#[cfg(feature = "full")]
+use crate::public_client::Currency;
+use std::convert::TryFrom;
+
+/// Rounds a decimal amount down to a multiple of `increment`, e.g. the `base_increment` or `quote_increment` of a `Product`
+/// <br>
+/// The result has as many decimals as the increment, ignoring its trailing zeros. Rounding is done on the decimal digits rather than with floating point, so no precision is lost. Amounts in scientific notation, such as `1E-8`, are accepted
+/// <br>
+/// ~~~~
+/// assert_eq!(round_to_increment("0.123456789", "0.00000001").unwrap(), "0.12345678");
+/// assert_eq!(round_to_increment("36000.129", "0.01000000").unwrap(), "36000.12");
+/// ~~~~
+pub fn round_to_increment(value: &str, increment: &str) -> Result<String, Error> {
+    let (value_digits, value_scale) = parse_decimal(value)
+        .ok_or_else(|| invalid_parameter(format!("{:?} is not a valid amount", value)))?;
+    let (mut increment_digits, mut increment_scale) = parse_decimal(increment)
+        .ok_or_else(|| invalid_parameter(format!("{:?} is not a valid increment", increment)))?;
+    // trailing zeros of an increment, e.g. 0.01000000, don't make it any finer
+    while increment_scale > 0 && increment_digits != 0 && increment_digits % 10 == 0 {
+        increment_digits /= 10;
+        increment_scale -= 1;
+    }
+    if increment_digits == 0 {
+        return Err(invalid_parameter(
+            "increment must be greater than zero".to_string(),
+        ));
+    }
+
+    // bring both to the same number of decimals so they can be divided as integers
+    let scale = value_scale.max(increment_scale);
+    let overflow = || invalid_parameter(format!("{:?} has too many digits", value));
+    let value_digits = value_digits
+        .checked_mul(pow10(scale - value_scale).ok_or_else(overflow)?)
+        .ok_or_else(overflow)?;
+    let increment_digits = increment_digits
+        .checked_mul(pow10(scale - increment_scale).ok_or_else(overflow)?)
+        .ok_or_else(overflow)?;
+    let rounded = value_digits / increment_digits * increment_digits
+        / pow10(scale - increment_scale).ok_or_else(overflow)?;
+
+    Ok(format_decimal(rounded, increment_scale))
+}
+
+/// Formats an amount of a currency with the currency's precision followed by its id, e.g. for displaying the balance of an `Account`
+/// <br>
+/// The amount is rounded down to the `max_precision` of the currency, like `round_to_increment`, so a balance is never overstated. Negative amounts, such as those of account history, keep their sign
+/// <br>
+/// ~~~~
+/// let client = PublicClient::new();
+/// let currency = client.get_currency("BTC").await.unwrap();
+/// assert_eq!(format_amount("0.123456789", &currency).unwrap(), "0.12345678 BTC");
+/// ~~~~
+#[cfg(feature = "full")]
+pub fn format_amount(value: &str, currency: &Currency) -> Result<String, Error> {
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(n) => ("-", n),
+        None => ("", value),
+    };
+    Ok(format!(
+        "{}{} {}",
+        sign,
+        round_to_increment(value, &currency.max_precision)?,
+        currency.id
+    ))
+}
+
+// checks an amount is a plain decimal, coinbase rejects amounts in other formats such as `1e-7`
+#[cfg(feature = "full")]
+pub(crate) fn check_amount(amount: &str) -> Result<(), Error> {
+    let is_plain = !amount.contains(['e', 'E']);
+    match parse_decimal(amount) {
+        Some(_) if is_plain => Ok(()),
+        _ => Err(invalid_parameter(format!(
+            "{:?} is not a valid amount, amounts must be plain decimals such as \"0.3\"",
+            amount
+        ))),
+    }
+}
+
+// whether `a + b == total` exactly, `false` if any amount isn't a non-negative decimal
+#[cfg(feature = "full")]
+pub(crate) fn is_sum(total: &str, a: &str, b: &str) -> bool {
+    let amounts = [total, a, b]
+        .iter()
+        .map(|n| parse_decimal(n))
+        .collect::<Option<Vec<_>>>();
+    let amounts = match amounts {
+        Some(n) => n,
+        None => return false,
+    };
+    let scale = amounts.iter().map(|(_, scale)| *scale).max().unwrap_or(0);
+    // each amount with `scale` digits after the decimal point
+    let scaled = amounts
+        .iter()
+        .map(|(digits, n)| digits.checked_mul(pow10(scale - n)?))
+        .collect::<Option<Vec<_>>>();
+    match scaled.as_deref() {
+        Some([total, a, b]) => a.checked_add(*b) == Some(*total),
+        _ => false,
+    }
+}
+
+// parses a non-negative decimal to its digits as an integer and the number of digits after the decimal point
+// coinbase sometimes returns very small amounts in scientific notation, e.g. `1E-8`, so an exponent is accepted
+fn parse_decimal(s: &str) -> Option<(u128, u32)> {
+    let (s, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |n: &str| n.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty() && fraction.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+        return None;
+    }
+    let digits = format!("{}{}", integer, fraction).parse::<u128>().ok()?;
+    let scale = i64::from(fraction.len() as u32) - i64::from(exponent);
+    if scale >= 0 {
+        Some((digits, u32::try_from(scale).ok()?))
+    } else {
+        let shift = u32::try_from(-scale).ok()?;
+        Some((digits.checked_mul(pow10(shift)?)?, 0))
+    }
+}
+
+// formats digits with `scale` digits after the decimal point
+fn format_decimal(digits: u128, scale: u32) -> String {
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    if scale == 0 {
+        return digits;
+    }
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}.{}", integer, fraction)
+}
+
+fn pow10(exponent: u32) -> Option<u128> {
+    10u128.checked_pow(exponent)
+}
+
+fn invalid_parameter(message: String) -> Error {
+    Error::new(ErrorKind::InvalidParameter(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scientific_notation() {
+        assert_eq!(parse_decimal("1E-8"), Some((1, 8)));
+        assert_eq!(parse_decimal("1.0E-8"), Some((10, 9)));
+        assert_eq!(parse_decimal("1.5e3"), Some((1500, 0)));
+        assert_eq!(parse_decimal("2.5e+1"), Some((25, 0)));
+        assert_eq!(parse_decimal("1e"), None);
+        assert_eq!(parse_decimal("e-8"), None);
+        assert_eq!(round_to_increment("1.5e3", "0.01").unwrap(), "1500.00");
+        assert_eq!(
+            round_to_increment("0.123456789", "1E-8").unwrap(),
+            "0.12345678"
+        );
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_scientific_notation_amounts() {
+        assert!(is_sum("0.00000002", "1E-8", "0.00000001"));
+        // amounts sent to coinbase must still be plain decimals
+        assert!(check_amount("0.00000001").is_ok());
+        assert!(check_amount("1E-8").is_err());
+        assert_eq!(crate::parse_amount("1E-8").unwrap(), 1e-8);
+        assert_eq!(crate::parse_amount("1.5e3").unwrap(), 1500.0);
+    }
+}
@@ -1,17 +1,45 @@
 //! A library for the Coinbase Pro [API](https://docs.pro.coinbase.com/).
 //!   
 //! **Coinbase Client** is separated into two categories: `PrivateClient` and `PublicClient`. `PrivateClient` requires authentication and provide access to placing orders and other account information. `PublicClient` provides market data and is public.
+//!
+//! The response structures, `PublicClient` and the typed methods of `PrivateClient` are behind the default `full` feature. Without it `PrivateClient::request` and `PrivateClient::request_bytes` can still send signed requests, returning a deserialized or raw response body, without depending on chrono.
+//!
+//! The `websocket` module, behind the `websocket` feature, streams the websocket feed and maintains order books from it.
+//!
+//! `Order` and `Report` are re-exported at the crate root since they are the request bodies of `PrivateClient::place_order` and `PrivateClient::create_report`. Create them with `OrderBuilder` and `ReportBuilder`.
+#[cfg(feature = "full")]
+mod cache;
+mod config;
 pub mod error;
+mod increment;
 pub mod private_client;
+mod product_id;
+#[cfg(feature = "full")]
 pub mod public_client;
+mod rate_limit;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+#[cfg(feature = "full")]
+pub use self::increment::format_amount;
+pub use self::increment::round_to_increment;
+pub use self::private_client::{Order, Report};
+pub use self::product_id::ProductId;
 
 use self::error::{Error, ErrorKind, ErrorMessage, StatusError};
+#[cfg(feature = "full")]
 use chrono::{DateTime, TimeZone, Utc};
+#[cfg(feature = "full")]
 use serde::{Deserialize, Deserializer};
 
 pub(crate) const COINBASE_API_URL: &'static str = "https://api.pro.coinbase.com";
 pub(crate) const COINBASE_SANDBOX_API_URL: &'static str =
     "https://api-public.sandbox.pro.coinbase.com";
+#[cfg(feature = "websocket")]
+pub(crate) const COINBASE_WEBSOCKET_URL: &str = "wss://ws-feed.pro.coinbase.com";
+#[cfg(feature = "websocket")]
+pub(crate) const COINBASE_SANDBOX_WEBSOCKET_URL: &str =
+    "wss://ws-feed-public.sandbox.pro.coinbase.com";
 
 /// alias for serde_json::Value return type for data that cannot predictably deserialized into a strongly typed struct
 pub type Json = serde_json::Value;
@@ -21,28 +49,71 @@ pub(crate) async fn deserialize_response<T>(response: reqwest::Response) -> Resu
 where
     T: serde::de::DeserializeOwned,
 {
+    let response = check_status(response).await?;
+    if !is_json(&response) {
+        return Err(non_json_error(response).await);
+    }
+    Ok(response.json::<T>().await?)
+}
+
+// returns the response if its status is successful, otherwise an error with the message coinbase responded with
+pub(crate) async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, Error> {
     let status = response.status();
     if !status.is_success() {
+        if !is_json(&response) {
+            return Err(non_json_error(response).await);
+        }
         let error_message = response.json::<ErrorMessage>().await?;
-        return Err(Error::new(ErrorKind::Status(StatusError::new(
-            status.as_u16(),
-            error_message.message,
-        ))));
+        return Err(Error::new(ErrorKind::Status(StatusError {
+            code: status.as_u16(),
+            message: error_message.message,
+            details: error_message.details,
+        })));
     }
 
-    Ok(response.json::<T>().await?)
+    Ok(response)
+}
+
+// whether the response may be json, during maintenance coinbase responds with html pages instead
+fn is_json(response: &reqwest::Response) -> bool {
+    match response.headers().get(reqwest::header::CONTENT_TYPE) {
+        Some(content_type) => content_type.to_str().unwrap_or_default().contains("json"),
+        None => true,
+    }
+}
+
+// a status error for a response which isn't json, with the start of the body as the message
+async fn non_json_error(response: reqwest::Response) -> Error {
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+    let message = body.trim().chars().take(200).collect();
+    Error::new(ErrorKind::Status(StatusError::new(status, message)))
+}
+
+// parses a decimal amount string returned by the API, e.g. an account balance
+#[cfg(feature = "full")]
+pub(crate) fn parse_amount(amount: &str) -> Result<f64, Error> {
+    Ok(serde_json::from_str::<f64>(amount)?)
 }
 
 // deserializes a ISO 8601 / RFC 3339 date & time format str to a DateTime<Utc>
+// transfers have dates such as `2019-06-18 01:37:48.78953+00` instead, which are accepted as well
+#[cfg(feature = "full")]
 pub(crate) fn deserialize_to_date<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
     Utc.datetime_from_str(&s, "%+")
+        .or_else(|e| {
+            DateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S%.f%#z")
+                .map(|n| n.with_timezone(&Utc))
+                .map_err(|_| e)
+        })
         .map_err(serde::de::Error::custom)
 }
 
+#[cfg(feature = "full")]
 pub(crate) fn deserialize_option_to_date<'de, D>(
     deserializer: D,
 ) -> Result<Option<DateTime<Utc>>, D::Error>
@@ -55,12 +126,26 @@ where
     Ok(v.map(|Wrapper(a)| a))
 }
 
+// the most results a paginated request can return
+#[cfg(feature = "full")]
+pub(crate) const MAX_PAGINATION_LIMIT: u16 = 100;
+
+// builds the query string of a paginated request, a limit above `MAX_PAGINATION_LIMIT` is an error rather than being capped by coinbase
+#[cfg(feature = "full")]
 pub(crate) fn configure_pagination(
     before: Option<&str>,
     after: Option<&str>,
     limit: Option<u16>,
-) -> String {
-    match (before, after, limit) {
+) -> Result<String, Error> {
+    if let Some(l) = limit {
+        if l > MAX_PAGINATION_LIMIT {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "limit {} is greater than the maximum of {}",
+                l, MAX_PAGINATION_LIMIT
+            ))));
+        }
+    }
+    Ok(match (before, after, limit) {
         (None, None, None) => String::from(""),
         (None, None, Some(l)) => format!("limit={}", l),
         (None, Some(a), None) => format!("after={}", a),
@@ -69,5 +154,75 @@ pub(crate) fn configure_pagination(
         (Some(b), None, Some(l)) => format!("before={}&limit={}", b, l),
         (Some(b), Some(a), None) => format!("before={}&after={}", b, a),
         (Some(b), Some(a), Some(l)) => format!("before={}&after={}&limit={}", b, a, l),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn html_response(status: u16) -> reqwest::Response {
+        http::Response::builder()
+            .status(status)
+            .header(reqwest::header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body("<html><body>Coinbase Pro is down for maintenance</body></html>")
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_html_response() {
+        for status in [200, 503] {
+            let error = deserialize_response::<Json>(html_response(status))
+                .await
+                .unwrap_err();
+            match error.kind {
+                ErrorKind::Status(err) => {
+                    assert_eq!(err.code, status);
+                    assert!(err.message.contains("maintenance"));
+                }
+                _ => panic!("expected a status error"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_json_error_response() {
+        let response: reqwest::Response = http::Response::builder()
+            .status(404)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(r#"{"message":"NotFound"}"#)
+            .unwrap()
+            .into();
+        let error = deserialize_response::<Json>(response).await.unwrap_err();
+        match error.kind {
+            ErrorKind::Status(err) => {
+                assert_eq!(err.message, "NotFound");
+                assert!(err.details.is_empty());
+            }
+            _ => panic!("expected a status error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_error_response_details() {
+        let response: reqwest::Response = http::Response::builder()
+            .status(400)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(r#"{"message":"Invalid order","reason":"size is too small. Minimum size is 0.001"}"#)
+            .unwrap()
+            .into();
+        let error = deserialize_response::<Json>(response).await.unwrap_err();
+        assert!(error.to_string().contains("size is too small"));
+        match error.kind {
+            ErrorKind::Status(err) => {
+                assert_eq!(err.message, "Invalid order");
+                assert_eq!(
+                    err.details["reason"],
+                    "size is too small. Minimum size is 0.001"
+                );
+            }
+            _ => panic!("expected a status error"),
+        }
     }
 }
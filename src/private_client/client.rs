@@ -0,0 +1,894 @@
+#[cfg(feature = "full")]
+use crate::cache::Cache;
+use crate::config::ClientConfig;
+use crate::error::{Error, ErrorKind};
+use crate::rate_limit::RateLimiter;
+use crate::{check_status, deserialize_response, COINBASE_API_URL, COINBASE_SANDBOX_API_URL};
+use base64;
+use bytes::Bytes;
+use crypto::{self, mac::Mac};
+use reqwest;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, SystemTimeError};
+
+const DEFAULT_USER_AGENT: &str = "coinbase-client";
+
+/// `PrivateClient` requires authentication and provide access to placing orders and other account information
+/// <br>
+/// With the `zeroize` feature the secret and passphrase held by the client are zeroed when it is dropped. Copies made while sending a request, such as the `cb-access-passphrase` header, aren't zeroed
+#[derive(Clone)]
+pub struct PrivateClient {
+    pub(super) reqwest_client: reqwest::Client,
+    user_agent: reqwest::header::HeaderValue,
+    secret: Credential,
+    passphrase: Credential,
+    key: String,
+    url: String,
+    // milliseconds the server clock is ahead of the local clock, shared by clones of the client
+    clock_offset: Arc<Mutex<Option<i64>>>,
+    rate_limiter: Option<RateLimiter>,
+    #[cfg(feature = "full")]
+    cache: Option<Cache>,
+}
+
+impl PrivateClient {
+    #[cfg(feature = "full")]
+    pub(super) async fn get<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.request(reqwest::Method::GET, path, None::<()>).await
+    }
+
+    // like `get`, also returning the `CB-AFTER` cursor of the response, which is the `after` of the next, older, page
+    #[cfg(feature = "full")]
+    pub(super) async fn get_page<T>(&self, path: &str) -> Result<(T, Option<String>), Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = self.send_request(reqwest::Method::GET, path, None).await?;
+        let after = response
+            .headers()
+            .get("cb-after")
+            .and_then(|n| n.to_str().ok())
+            .map(|n| n.to_string());
+        Ok((deserialize_response(response).await?, after))
+    }
+
+    // gets path from the cache if the client has one, otherwise like `get`
+    #[cfg(feature = "full")]
+    pub(super) async fn get_cached<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let cache = match self.cache {
+            Some(ref n) => n,
+            None => return self.get(path).await,
+        };
+        let body = match cache.get(path) {
+            Some(n) => n,
+            None => {
+                let body = self.request_bytes(reqwest::Method::GET, path, None).await?;
+                cache.insert(path, body.clone());
+                body
+            }
+        };
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    #[cfg(feature = "full")]
+    pub(super) async fn post_and_deserialize<T, K>(
+        &self,
+        path: &str,
+        body: Option<K>,
+    ) -> Result<T, Error>
+    where
+        K: serde::Serialize,
+        T: serde::de::DeserializeOwned,
+    {
+        self.request(reqwest::Method::POST, path, body).await
+    }
+
+    // posts to an endpoint which doesn't respond with json, returning the response if its status is successful
+    #[cfg(feature = "full")]
+    pub(super) async fn post<K>(
+        &self,
+        path: &str,
+        body: Option<K>,
+    ) -> Result<reqwest::Response, Error>
+    where
+        K: serde::Serialize,
+    {
+        // serialize the body once so the signed body is exactly the sent body
+        let body = body.map(|n| serde_json::to_string(&n)).transpose()?;
+        check_status(
+            self.send_request(reqwest::Method::POST, path, body.as_deref())
+                .await?,
+        )
+        .await
+    }
+
+    #[cfg(feature = "full")]
+    pub(super) async fn delete<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.request(reqwest::Method::DELETE, path, None::<()>)
+            .await
+    }
+
+    /// Signs and sends a request to `path`, serializing `body` to json and deserializing the response to `T`
+    /// <br>
+    /// The typed methods are built on `request`, it can be used for endpoints which the client doesn't provide a method for yet
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let accounts: serde_json::Value = client
+    ///     .request(reqwest::Method::GET, "/accounts", None::<()>)
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn request<T, B>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<B>,
+    ) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        // serialize the body once so the signed body is exactly the sent body
+        let body = body.map(|n| serde_json::to_string(&n)).transpose()?;
+        deserialize_response::<T>(self.send_request(method, path, body.as_deref()).await?).await
+    }
+
+    /// Signs and sends a request to `path`, returning the raw bytes of the response body
+    /// <br>
+    /// This is available without the `full` feature, which provides the typed methods and response structures
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let accounts = client.request_bytes(reqwest::Method::GET, "/accounts", None).await.unwrap();
+    /// ~~~~
+    pub async fn request_bytes(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&str>,
+    ) -> Result<Bytes, Error> {
+        let response = check_status(self.send_request(method, path, body).await?).await?;
+        Ok(response.bytes().await?)
+    }
+
+    // builds a request to path with an optional json body, then signs and sends it
+    async fn send_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&str>,
+    ) -> Result<reqwest::Response, Error> {
+        let request_builder = self
+            .reqwest_client
+            .request(method, format!("{}{}", self.url, path));
+        let request = match body {
+            Some(n) => request_builder
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(n.to_owned())
+                .build()?,
+            None => request_builder.build()?,
+        };
+        self.send(request, body).await
+    }
+
+    // signs and sends a request
+    async fn send(
+        &self,
+        mut request: reqwest::Request,
+        body: Option<&str>,
+    ) -> Result<reqwest::Response, Error> {
+        // sign the path as it will be sent, after reqwest has percent-encoded it
+        let path = request_path(request.url());
+        // wait before signing, a request waiting on the rate limit could otherwise have a stale timestamp
+        self.acquire_rate_limit().await;
+        let headers = self.access_headers(&path, body, request.method().as_str())?;
+        request.headers_mut().extend(headers);
+        Ok(self.reqwest_client.execute(request).await?)
+    }
+
+    async fn acquire_rate_limit(&self) {
+        if let Some(ref n) = self.rate_limiter {
+            n.acquire().await;
+        }
+    }
+
+    fn get_current_timestamp(&self) -> Result<String, SystemTimeError> {
+        let offset = self.clock_offset.lock().unwrap().unwrap_or(0);
+        Ok(((unix_millis()? + offset) / 1000).to_string())
+    }
+
+    /// Measures the offset between the coinbase server clock and the local clock, which is applied to the timestamp of each following request
+    /// <br>
+    /// Coinbase rejects requests with a timestamp more than 30 seconds from the server time with a 401, calling `sync_time` once after creating the client avoids this when the local clock is wrong. The request to `/time` isn't signed, so it succeeds regardless of the local clock
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#time)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// client.sync_time().await.unwrap();
+    /// ~~~~
+    #[cfg(feature = "full")]
+    pub async fn sync_time(&self) -> Result<(), Error> {
+        self.acquire_rate_limit().await;
+        let sent = unix_millis()?;
+        let response = self
+            .reqwest_client
+            .get(format!("{}/time", self.url))
+            .header(reqwest::header::USER_AGENT, self.user_agent.clone())
+            .send()
+            .await?;
+        let received = unix_millis()?;
+        let time: crate::public_client::Time = deserialize_response(response).await?;
+        // assume the server read its clock halfway through the round trip
+        let offset = (time.epoch * 1000.0) as i64 - (sent + received) / 2;
+        *self.clock_offset.lock().unwrap() = Some(offset);
+        Ok(())
+    }
+
+    /// Returns `true` if the client sends requests to the coinbase pro sandbox API, e.g. to refuse to place live orders in a dry run
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new_sandbox("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// assert!(client.is_sandbox());
+    /// ~~~~
+    pub fn is_sandbox(&self) -> bool {
+        self.url == COINBASE_SANDBOX_API_URL
+    }
+
+    /// Returns the milliseconds the coinbase server clock is ahead of the local clock as measured by `sync_time`, negative if the local clock is ahead, or `None` if `sync_time` hasn't been called
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// client.sync_time().await.unwrap();
+    /// if client.clock_skew().unwrap() < 0 {
+    ///     println!("the local clock is ahead of coinbase");
+    /// }
+    /// ~~~~
+    pub fn clock_skew(&self) -> Option<i64> {
+        *self.clock_offset.lock().unwrap()
+    }
+
+    /// Clears the responses cached because of `cache_ttl`, so the next request of each gets fresh data
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::builder("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef")
+    ///     .cache_ttl(Duration::from_secs(30))
+    ///     .build()
+    ///     .unwrap();
+    /// client.clear_cache();
+    /// ~~~~
+    #[cfg(feature = "full")]
+    pub fn clear_cache(&self) {
+        if let Some(ref n) = self.cache {
+            n.clear();
+        }
+    }
+
+    fn access_headers(
+        &self,
+        url: &str,
+        body: Option<&str>,
+        method: &str,
+    ) -> Result<reqwest::header::HeaderMap, Error> {
+        let timestamp = self.get_current_timestamp()?;
+        let signature = self.sign_message(url, body, &timestamp, method)?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::USER_AGENT, self.user_agent.clone());
+        headers.insert(
+            reqwest::header::HeaderName::from_static("cb-access-key"),
+            header_value("cb-access-key", &self.key)?,
+        );
+        headers.insert(
+            reqwest::header::HeaderName::from_static("cb-access-sign"),
+            header_value("cb-access-sign", &signature)?,
+        );
+        headers.insert(
+            reqwest::header::HeaderName::from_static("cb-access-timestamp"),
+            header_value("cb-access-timestamp", &timestamp)?,
+        );
+        // the header value is a copy of the passphrase which isn't zeroed, marking it sensitive keeps it out of debug output
+        let mut passphrase = header_value("cb-access-passphrase", self.passphrase.expose())?;
+        passphrase.set_sensitive(true);
+        headers.insert(
+            reqwest::header::HeaderName::from_static("cb-access-passphrase"),
+            passphrase,
+        );
+
+        Ok(headers)
+    }
+
+    fn sign_message(
+        &self,
+        url: &str,
+        body: Option<&str>,
+        timestamp: &str,
+        method: &str,
+    ) -> Result<String, Error> {
+        let mut prehash = String::new();
+        // omit body if not supplied
+        match body {
+            Some(body) => {
+                prehash.push_str(&timestamp);
+                prehash.push_str(&method);
+                prehash.push_str(&url);
+                prehash.push_str(&body);
+            }
+            None => {
+                prehash.push_str(&timestamp);
+                prehash.push_str(&method);
+                prehash.push_str(&url);
+            }
+        }
+        self.sign_message_bytes(prehash.as_bytes())
+    }
+
+    // adds the fields authenticating a websocket subscription, the signature of a GET of `/users/self/verify`
+    #[cfg(feature = "websocket")]
+    pub(crate) fn sign_subscription(&self, subscribe: &mut crate::Json) -> Result<(), Error> {
+        let timestamp = self.get_current_timestamp()?;
+        subscribe["signature"] = self
+            .sign_message("/users/self/verify", None, &timestamp, "GET")?
+            .into();
+        subscribe["key"] = self.key.clone().into();
+        subscribe["passphrase"] = self.passphrase.expose().into();
+        subscribe["timestamp"] = timestamp.into();
+        Ok(())
+    }
+
+    /// Signs `prehash` with the API secret the way requests are signed, returning the base 64 encoded hmac-sha256
+    /// <br>
+    /// Requests are signed with the prehash `timestamp + method + request path + body`, signing the bytes allows bodies which aren't valid UTF-8, e.g. to authenticate a websocket subscription or sign a webhook. Returns an error of kind `ErrorKind::InvalidParameter` if the secret isn't valid base 64
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let signature = client.sign_message_bytes(b"1623952522GET/users/self/verify").unwrap();
+    /// ~~~~
+    pub fn sign_message_bytes(&self, prehash: &[u8]) -> Result<String, Error> {
+        let hmac_result = hmac_sha256(self.secret.expose(), prehash)?;
+        let hmac_code = hmac_result.code();
+        let base64_encoding = base64::encode(hmac_code);
+        // return base64 encoded hmac result
+        Ok(base64_encoding)
+    }
+
+    /// Creates a new `PrivateClient`
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// ~~~~
+    pub fn new(
+        secret: impl Into<String>,
+        passphrase: impl Into<String>,
+        key: impl Into<String>,
+    ) -> Self {
+        Self {
+            reqwest_client: ClientConfig::default_reqwest_client(),
+            user_agent: reqwest::header::HeaderValue::from_static(DEFAULT_USER_AGENT),
+            secret: Credential(secret.into()), // shared secret
+            key: key.into(),
+            passphrase: Credential(passphrase.into()),
+            url: COINBASE_API_URL.to_string(),
+            clock_offset: Arc::new(Mutex::new(None)),
+            rate_limiter: None,
+            #[cfg(feature = "full")]
+            cache: None,
+        }
+    }
+
+    /// Creates a new `PrivateClient` for testing API connectivity and web trading
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// ~~~~
+    pub fn new_sandbox(
+        secret: impl Into<String>,
+        passphrase: impl Into<String>,
+        key: impl Into<String>,
+    ) -> Self {
+        Self {
+            reqwest_client: ClientConfig::default_reqwest_client(),
+            user_agent: reqwest::header::HeaderValue::from_static(DEFAULT_USER_AGENT),
+            secret: Credential(secret.into()),
+            key: key.into(),
+            passphrase: Credential(passphrase.into()),
+            url: COINBASE_SANDBOX_API_URL.to_string(),
+            clock_offset: Arc::new(Mutex::new(None)),
+            rate_limiter: None,
+            #[cfg(feature = "full")]
+            cache: None,
+        }
+    }
+
+    /// Creates a new `PrivateClient` with credentials read from the environment, so they aren't kept in source code
+    /// <br>
+    /// The secret, passphrase and key are read from `COINBASE_API_SECRET`, `COINBASE_API_PASSPHRASE` and `COINBASE_API_KEY`. The sandbox API is used if `COINBASE_SANDBOX` is set to `1` or `true`. Returns an error of kind `ErrorKind::InvalidParameter` if a credential isn't set
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::from_env().unwrap();
+    /// ~~~~
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
+
+    // creates a client with the variables returned by `lookup`, which reads the environment outside of tests
+    fn from_env_with(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let var = |name: &str| {
+            lookup(name).ok_or_else(|| {
+                Error::new(ErrorKind::InvalidParameter(format!(
+                    "environment variable {} is not set",
+                    name
+                )))
+            })
+        };
+        let builder = Self::builder(
+            var("COINBASE_API_SECRET")?,
+            var("COINBASE_API_PASSPHRASE")?,
+            var("COINBASE_API_KEY")?,
+        );
+        match lookup("COINBASE_SANDBOX").as_deref() {
+            Some("1") | Some("true") => builder.sandbox().build(),
+            _ => builder.build(),
+        }
+    }
+
+    /// Creates a `PrivateClientBuilder` to configure a `PrivateClient`
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::builder("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef")
+    ///     .pool_idle_timeout(Duration::from_secs(300))
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn builder(
+        secret: impl Into<String>,
+        passphrase: impl Into<String>,
+        key: impl Into<String>,
+    ) -> PrivateClientBuilder {
+        PrivateClientBuilder {
+            config: ClientConfig::default(),
+            secret: secret.into(),
+            passphrase: passphrase.into(),
+            key: key.into(),
+            url: COINBASE_API_URL.to_string(),
+        }
+    }
+}
+
+// the credentials are redacted so they aren't leaked when a client is logged
+impl std::fmt::Debug for PrivateClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrivateClient")
+            .field("secret", &"***")
+            .field("passphrase", &"***")
+            .field("key", &"***")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+/// A `PrivateClientBuilder` can be used to create a `PrivateClient` with custom configuration.
+#[must_use = "a builder does nothing until it is built"]
+pub struct PrivateClientBuilder {
+    config: ClientConfig,
+    secret: String,
+    passphrase: String,
+    key: String,
+    url: String,
+}
+
+impl PrivateClientBuilder {
+    /// Use the coinbase pro sandbox API
+    pub fn sandbox(mut self) -> Self {
+        self.url = COINBASE_SANDBOX_API_URL.to_string();
+        self
+    }
+
+    /// Use a custom base url, e.g. a gateway which proxies requests to the coinbase pro API
+    /// <br>
+    /// Requests are sent to the base url followed by the path of the endpoint
+    /// <br>
+    /// The path of each request is signed as sent, so the gateway must forward requests to coinbase with the same path
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets how long idle connections are kept alive in the connection pool (default is 90 seconds)
+    /// <br>
+    /// Pass `None` to keep idle connections alive indefinitely
+    pub fn pool_idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.config.pool_idle_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host (default is unlimited)
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.config.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets the user agent sent with each request (default is `coinbase-client`)
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.config.user_agent = Some(user_agent);
+        self
+    }
+
+    /// Sets whether gzip and brotli compressed responses are accepted (default is `true`)
+    /// <br>
+    /// Compressed responses are decompressed before they are deserialized, disabling compression can be useful to inspect responses while debugging
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.config.compression = enabled;
+        self
+    }
+
+    /// Routes requests through `proxy`, call multiple times to add proxies for different schemes
+    /// <br>
+    /// The `HTTP_PROXY` and `HTTPS_PROXY` environment variables are ignored unless `env_proxy` is called
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::builder("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef")
+    ///     .proxy(reqwest::Proxy::https("http://proxy.example.com:8080").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.config.proxies.push(proxy);
+        self
+    }
+
+    /// Routes requests through the proxies of the `HTTP_PROXY` and `HTTPS_PROXY` environment variables (default is no proxy)
+    /// <br>
+    /// Adding a proxy with `proxy` replaces the proxies of the environment
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::builder("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef")
+    ///     .env_proxy()
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn env_proxy(mut self) -> Self {
+        self.config.env_proxy = true;
+        self
+    }
+
+    /// Limits requests to `requests_per_second`, allowing bursts of up to `burst` requests (default is unlimited)
+    /// <br>
+    /// Requests wait until they are within the limit rather than being rejected by coinbase with a 429. Clones of the client share the limit, so it holds when the client is cloned across tasks, but separately built clients are each limited on their own
+    pub fn rate_limit(mut self, requests_per_second: u32, burst: u32) -> Self {
+        self.config.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// Caches the responses of `get_accounts` for `ttl` (default is no caching)
+    /// <br>
+    /// A cached response is returned rather than sending a request until it is older than `ttl`, so the data may be up to `ttl` old. Clones of the client share the cache, call `clear_cache` to get fresh data before the ttl expires
+    #[cfg(feature = "full")]
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Builds `PrivateClient`
+    pub fn build(self) -> Result<PrivateClient, Error> {
+        Ok(PrivateClient {
+            reqwest_client: self.config.build_reqwest_client()?,
+            user_agent: self.config.user_agent(DEFAULT_USER_AGENT)?,
+            secret: Credential(self.secret),
+            passphrase: Credential(self.passphrase),
+            key: self.key,
+            url: self.url,
+            clock_offset: Arc::new(Mutex::new(None)),
+            rate_limiter: self.config.rate_limiter()?,
+            #[cfg(feature = "full")]
+            cache: self.config.cache(),
+        })
+    }
+}
+
+/// Verifies a signature made the same way `PrivateClient` signs its requests, e.g. to verify a webhook
+/// <br>
+/// The hmac-sha256 of `prehash`, keyed with the base 64 decoded `secret`, is compared in constant time to the base 64 decoded `provided_signature`. Returns `false` if either the secret or the signature isn't valid base 64
+/// <br>
+/// ~~~~
+/// let valid = verify_signature(
+///     "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==",
+///     "1623952522GET/accounts",
+///     "B4a/mfq0mHqlbIkg482oWrcAQGq1Bi5todPFjSO+lxA=",
+/// );
+/// ~~~~
+pub fn verify_signature(secret: &str, prehash: &str, provided_signature: &str) -> bool {
+    let provided_signature = match base64::decode(provided_signature) {
+        Ok(n) => n,
+        Err(_) => return false,
+    };
+    match hmac_sha256(secret, prehash.as_bytes()) {
+        // MacResult compares in constant time
+        Ok(n) => n == crypto::mac::MacResult::new(&provided_signature),
+        Err(_) => false,
+    }
+}
+
+// milliseconds since the unix epoch by the local clock
+fn unix_millis() -> Result<i64, SystemTimeError> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_millis() as i64)
+}
+
+// hmac-sha256 of the prehash keyed with the base 64 decoded secret
+fn hmac_sha256(secret: &str, prehash: &[u8]) -> Result<crypto::mac::MacResult, Error> {
+    // decode your coinbase api secret
+    let decoded_secret = base64::decode(secret).map_err(|_| {
+        Error::new(ErrorKind::InvalidParameter(
+            "unable to decode secret, is your secret in base 64 encoding".to_string(),
+        ))
+    })?;
+    // zeroed when dropped at the end of the function
+    #[cfg(feature = "zeroize")]
+    let decoded_secret = zeroize::Zeroizing::new(decoded_secret);
+    // hmac-sha256 it
+    let mut hmac = crypto::hmac::Hmac::new(crypto::sha2::Sha256::new(), &decoded_secret);
+    hmac.input(prehash);
+    Ok(hmac.result())
+}
+
+// a credential which, with the zeroize feature, is zeroed when dropped
+#[derive(Clone)]
+struct Credential(String);
+
+impl Credential {
+    fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Credential {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+// converts a header value, credentials are supplied by the user so may contain characters which aren't allowed in headers
+fn header_value(name: &str, value: &str) -> Result<reqwest::header::HeaderValue, Error> {
+    reqwest::header::HeaderValue::from_str(value).map_err(|_| {
+        Error::new(ErrorKind::InvalidParameter(format!(
+            "{} contains characters which are not allowed in a http header",
+            name
+        )))
+    })
+}
+
+// returns the path and query string of a url, the part of the url which is signed
+fn request_path(url: &reqwest::Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_client() -> PrivateClient {
+        PrivateClient::new(
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+            "t9riylyad0r".to_string(),
+            "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_timestamp_applies_clock_offset() {
+        let client = create_client();
+        assert_eq!(client.clock_skew(), None);
+        *client.clock_offset.lock().unwrap() = Some(-60_000);
+        let local = unix_millis().unwrap() / 1000;
+        let timestamp: i64 = client.get_current_timestamp().unwrap().parse().unwrap();
+        assert!((local - 61..=local - 59).contains(&timestamp));
+        assert_eq!(client.clock_skew(), Some(-60_000));
+    }
+
+    #[test]
+    fn test_sign_message_with_query_string() {
+        let client = create_client();
+        let signature = client
+            .sign_message(
+                "/fills?product_id=BTC-USD&before=29786034&limit=100",
+                None,
+                "1623952522",
+                "GET",
+            )
+            .unwrap();
+        assert_eq!(signature, "yhdjLi2Z6FtHD3HMm5XbEyzs0JS9Ds7k3egSahv8TwE=");
+    }
+
+    #[test]
+    fn test_sign_message_bytes() {
+        let client = create_client();
+        let signature = client
+            .sign_message_bytes(b"1623952522GET/fills?product_id=BTC-USD&before=29786034&limit=100")
+            .unwrap();
+        assert_eq!(signature, "yhdjLi2Z6FtHD3HMm5XbEyzs0JS9Ds7k3egSahv8TwE=");
+        // bodies needn't be valid UTF-8
+        assert!(client.sign_message_bytes(&[0xff, 0xfe]).is_ok());
+    }
+
+    #[test]
+    fn test_access_headers_invalid_passphrase() {
+        let client = PrivateClient::new(
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+            "t9riy\nlyad0r".to_string(),
+            "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+        );
+        let error = client.access_headers("/accounts", None, "GET").unwrap_err();
+        match error.kind {
+            ErrorKind::InvalidParameter(message) => assert!(message.contains("passphrase")),
+            _ => panic!("expected an invalid parameter error"),
+        }
+    }
+
+    #[test]
+    fn test_access_headers_invalid_secret() {
+        let client = PrivateClient::new(
+            "not base 64",
+            "t9riylyad0r",
+            "4a9f6de8bcdee641a0a207613dfb43ef",
+        );
+        assert!(client.access_headers("/accounts", None, "GET").is_err());
+    }
+
+    #[test]
+    fn test_request_path_matches_sent_path() {
+        let client = create_client();
+        let path = "/fills?product_id=BTC-USD&before=29786034&limit=100";
+        let request = client
+            .reqwest_client
+            .get(format!("{}{}", client.url, path))
+            .build()
+            .unwrap();
+        assert_eq!(request_path(request.url()), path);
+    }
+
+    #[test]
+    fn test_request_path_is_percent_encoded() {
+        let client = create_client();
+        let request = client
+            .reqwest_client
+            .get(format!("{}{}", client.url, "/orders/client:a b"))
+            .build()
+            .unwrap();
+        assert_eq!(request_path(request.url()), "/orders/client:a%20b");
+    }
+
+    #[test]
+    fn test_builder_url() {
+        let client = PrivateClient::builder(
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+            "t9riylyad0r".to_string(),
+            "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+        )
+        .url("https://gateway.example.com/")
+        .build()
+        .unwrap();
+        assert_eq!(client.url, "https://gateway.example.com");
+    }
+
+    #[test]
+    fn test_is_sandbox() {
+        let sandbox = PrivateClient::builder(
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+            "t9riylyad0r".to_string(),
+            "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+        )
+        .sandbox()
+        .build()
+        .unwrap();
+        assert!(sandbox.is_sandbox());
+        assert!(!create_client().is_sandbox());
+    }
+
+    #[test]
+    fn test_builder_rate_limit() {
+        let builder = || {
+            PrivateClient::builder(
+                "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+                "t9riylyad0r".to_string(),
+                "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+            )
+        };
+        let client = builder().rate_limit(5, 10).build().unwrap();
+        assert!(client.rate_limiter.is_some());
+        assert!(client.clone().rate_limiter.is_some());
+        assert!(create_client().rate_limiter.is_none());
+        let err = builder().rate_limit(0, 10).build().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::InvalidParameter(_)));
+    }
+
+    #[test]
+    fn test_from_env() {
+        let mut env = std::collections::HashMap::new();
+        env.insert(
+            "COINBASE_API_SECRET",
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==",
+        );
+        env.insert("COINBASE_API_PASSPHRASE", "t9riylyad0r");
+        env.insert("COINBASE_API_KEY", "4a9f6de8bcdee641a0a207613dfb43ef");
+        env.insert("COINBASE_SANDBOX", "true");
+        let client =
+            PrivateClient::from_env_with(|name| env.get(name).map(|n| n.to_string())).unwrap();
+        assert_eq!(client.key, "4a9f6de8bcdee641a0a207613dfb43ef");
+        assert_eq!(client.url, COINBASE_SANDBOX_API_URL);
+        env.remove("COINBASE_API_KEY");
+        let error =
+            PrivateClient::from_env_with(|name| env.get(name).map(|n| n.to_string())).unwrap_err();
+        match error.kind {
+            ErrorKind::InvalidParameter(message) => assert!(message.contains("COINBASE_API_KEY")),
+            _ => panic!("expected an invalid parameter error"),
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let secret =
+            "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==";
+        let prehash = "1623952522GET/fills?product_id=BTC-USD&before=29786034&limit=100";
+        assert!(verify_signature(
+            secret,
+            prehash,
+            "yhdjLi2Z6FtHD3HMm5XbEyzs0JS9Ds7k3egSahv8TwE="
+        ));
+        assert!(!verify_signature(
+            secret,
+            "1623952523GET/fills?product_id=BTC-USD&before=29786034&limit=100",
+            "yhdjLi2Z6FtHD3HMm5XbEyzs0JS9Ds7k3egSahv8TwE="
+        ));
+        assert!(!verify_signature(secret, prehash, ""));
+        assert!(!verify_signature(secret, prehash, "not base 64"));
+    }
+
+    #[test]
+    fn test_debug_redacts_credentials() {
+        let debug = format!("{:?}", create_client());
+        assert!(!debug.contains("tGJSu7SuV3"));
+        assert!(!debug.contains("t9riylyad0r"));
+        assert!(!debug.contains("4a9f6de8bcdee641a0a207613dfb43ef"));
+        assert!(debug.contains(COINBASE_API_URL));
+    }
+
+    #[test]
+    fn test_builder_user_agent() {
+        let builder = || {
+            PrivateClient::builder(
+                "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==".to_string(),
+                "t9riylyad0r".to_string(),
+                "4a9f6de8bcdee641a0a207613dfb43ef".to_string(),
+            )
+        };
+        let client = builder()
+            .user_agent("my-bot/1.0".to_string())
+            .build()
+            .unwrap();
+        let headers = client.access_headers("/accounts", None, "GET").unwrap();
+        assert_eq!(headers[reqwest::header::USER_AGENT], "my-bot/1.0");
+        let headers = create_client()
+            .access_headers("/accounts", None, "GET")
+            .unwrap();
+        assert_eq!(headers[reqwest::header::USER_AGENT], DEFAULT_USER_AGENT);
+        assert!(builder()
+            .user_agent("my-bot\n".to_string())
+            .build()
+            .is_err());
+    }
+}
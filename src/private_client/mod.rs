@@ -1,6 +1,10 @@
+pub use client::*;
 pub use order::*;
+#[cfg(feature = "full")]
 pub use private_client::*;
 pub use report::*;
+mod client;
 mod order;
+#[cfg(feature = "full")]
 mod private_client;
 mod report;
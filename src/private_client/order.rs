@@ -1,30 +1,71 @@
+use crate::error::{Error, ErrorKind};
+use crate::ProductId;
+#[cfg(feature = "full")]
+use crate::{public_client::Product, round_to_increment};
 use serde::Serialize;
+use std::convert::TryFrom;
+use std::str::FromStr;
 
-/// A `OrderBuilder` should be used to create a `Order` with  custom configuration.
+/// A structure that represents an order to be placed with `PrivateClient::place_order`
+/// <br>
+/// The fields of an `Order` are private, use `OrderBuilder` (or `Order::market_builder`, `Order::limit_builder` and `Order::stop_builder`) to create one.
+/// <br>
+/// ~~~~
+/// let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+/// ~~~~
+// fields which aren't set are left out of the request body rather than sent as null
 #[derive(Serialize, Debug)]
+#[must_use = "an order does nothing until it is placed with `PrivateClient::place_order`"]
 pub struct Order {
     r#type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     price: Option<String>,
     side: OrderSide,
+    #[serde(skip_serializing_if = "Option::is_none")]
     client_oid: Option<String>,
+    #[serde(rename = "stp", skip_serializing_if = "Option::is_none")]
     self_trade_prevention: Option<SelfTradePrevention>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     time_in_force: Option<TimeInForce>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     cancel_after: Option<CancelAfter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     post_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     funds: Option<String>,
     product_id: String,
-    stp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<OrderStop>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     stop_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile_id: Option<String>,
 }
 
 /// A `OrderBuilder` should be used to create a `Order` with  custom configuration.
 impl Order {
+    // a buy stop-loss or sell stop-entry would trigger on the price moving in favour of the order,
+    // which a plain limit order already does, so the stop direction must match the side
+    #[cfg(feature = "full")]
+    pub(super) fn check_stop(&self) -> Result<(), Error> {
+        match (self.side, self.stop) {
+            (OrderSide::Buy, Some(stop @ OrderStop::Loss))
+            | (OrderSide::Sell, Some(stop @ OrderStop::Entry)) => {
+                Err(Error::new(ErrorKind::InvalidParameter(format!(
+                    "{:?} stop doesn't match {:?} side, use loss with sell and entry with buy",
+                    stop, self.side
+                ))))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// returns a `OrderBuilder` with required market-order parameters, equivalent OrderBuilder::market
     pub fn market_builder(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         size_or_funds: SizeOrFunds,
     ) -> impl SharedOptions {
         OrderBuilder {
@@ -44,17 +85,17 @@ impl Order {
                 SizeOrFunds::Funds(ref n) => Some(n.to_owned()),
                 _ => None,
             },
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
             stop: None,
             stop_price: None,
+            profile_id: None,
         }
     }
 
     /// returns a `OrderBuilder` with required limit-order parameters, equivalent OrderBuilder::limit
     pub fn limit_builder(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         price: &str,
         size: &str,
     ) -> impl LimitOptions + SharedOptions {
@@ -69,17 +110,17 @@ impl Order {
             cancel_after: None,
             post_only: None,
             funds: None,
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
             stop: None,
             stop_price: None,
+            profile_id: None,
         }
     }
 
     /// returns a `OrderBuilder` with required stop-order parameters, equivalent OrderBuilder::stop
     pub fn stop_builder(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         price: &str,
         size: String,
         stop_price: &str,
@@ -96,17 +137,41 @@ impl Order {
             cancel_after: None,
             post_only: None,
             funds: None,
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
             stop: Some(stop),
             stop_price: Some(stop_price.to_owned()),
+            profile_id: None,
         }
     }
+
+    /// returns a `OrderBuilder` with required stop-limit-order parameters, equivalent OrderBuilder::stop_limit
+    pub fn stop_limit_builder(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        price: &str,
+        size: &str,
+        stop_price: &str,
+        stop: OrderStop,
+    ) -> impl LimitOptions + SharedOptions {
+        OrderBuilder::stop_limit(side, product_id, price, size, stop_price, stop)
+    }
+
+    /// returns a `OrderBuilder` with required stop-market-order parameters, equivalent OrderBuilder::stop_market
+    pub fn stop_market_builder(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        size_or_funds: SizeOrFunds,
+        stop_price: &str,
+        stop: OrderStop,
+    ) -> impl SharedOptions {
+        OrderBuilder::stop_market(side, product_id, size_or_funds, stop_price, stop)
+    }
 }
 
 /// A `OrderBuilder` can be used to create a `Order` with custom configuration.
 /// <br>
 /// Configuration parameters details can be found [here](https://docs.pro.coinbase.com/#orders)
+#[must_use = "a builder does nothing until it is built"]
 pub struct OrderBuilder {
     r#type: String,
     size: Option<String>,
@@ -119,16 +184,16 @@ pub struct OrderBuilder {
     post_only: Option<bool>,
     funds: Option<String>,
     product_id: String,
-    stp: Option<String>,
     stop: Option<OrderStop>,
     stop_price: Option<String>,
+    profile_id: Option<String>,
 }
 
 impl OrderBuilder {
     /// returns a `OrderBuilder` with required market-order parameters.
     pub fn market(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         size_or_funds: SizeOrFunds,
     ) -> impl SharedOptions {
         Self {
@@ -148,17 +213,35 @@ impl OrderBuilder {
                 SizeOrFunds::Funds(ref n) => Some(n.to_owned()),
                 _ => None,
             },
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
             stop: None,
             stop_price: None,
+            profile_id: None,
         }
     }
 
+    /// returns a `OrderBuilder` with required market-order parameters, ordering `size` of the base currency, equivalent to `OrderBuilder::market` with `SizeOrFunds::Size`
+    pub fn market_with_size(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        size: &str,
+    ) -> impl SharedOptions {
+        Self::market(side, product_id, SizeOrFunds::Size(size.to_owned()))
+    }
+
+    /// returns a `OrderBuilder` with required market-order parameters, spending `funds` of the quote currency, equivalent to `OrderBuilder::market` with `SizeOrFunds::Funds`
+    pub fn market_with_funds(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        funds: &str,
+    ) -> impl SharedOptions {
+        Self::market(side, product_id, SizeOrFunds::Funds(funds.to_owned()))
+    }
+
     /// returns a `OrderBuilder` with required limit-order parameters.
     pub fn limit(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         price: &str,
         size: &str,
     ) -> impl LimitOptions + SharedOptions {
@@ -173,45 +256,105 @@ impl OrderBuilder {
             cancel_after: None,
             post_only: None,
             funds: None,
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
             stop: None,
             stop_price: None,
+            profile_id: None,
         }
     }
 
-    /// returns a `OrderBuilder` with required stop-order parameters.
+    /// returns a `OrderBuilder` with required stop-order parameters, equivalent to `OrderBuilder::stop_limit`.
     pub fn stop(
         side: OrderSide,
-        product_id: &str,
+        product_id: impl Into<ProductId>,
         price: &str,
         size: &str,
         stop_price: &str,
         stop: OrderStop,
     ) -> impl SharedOptions {
+        Self::stop_limit(side, product_id, price, size, stop_price, stop)
+    }
+
+    /// returns a `OrderBuilder` with required stop-limit-order parameters.
+    /// <br>
+    /// Once the last trade price reaches `stop_price` a limit order is placed at `price`.
+    /// <br>
+    /// Use `OrderStop::Loss` with `OrderSide::Sell`, to sell when the price falls to `stop_price`,
+    /// and `OrderStop::Entry` with `OrderSide::Buy`, to buy when the price rises to `stop_price`.
+    /// Placing an order with the other combinations returns an error of kind `ErrorKind::InvalidParameter` without sending a request.
+    pub fn stop_limit(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        price: &str,
+        size: &str,
+        stop_price: &str,
+        stop: OrderStop,
+    ) -> impl LimitOptions + SharedOptions {
         Self {
             r#type: "limit".to_string(),
             size: Some(size.to_owned()),
             price: Some(price.to_owned()),
-            side: side,
+            side,
             client_oid: None,
             self_trade_prevention: None,
             time_in_force: None,
             cancel_after: None,
             post_only: None,
             funds: None,
-            product_id: product_id.to_string(),
-            stp: None,
+            product_id: product_id.into().to_string(),
+            stop: Some(stop),
+            stop_price: Some(stop_price.to_owned()),
+            profile_id: None,
+        }
+    }
+
+    /// returns a `OrderBuilder` with required stop-market-order parameters.
+    /// <br>
+    /// Once the last trade price reaches `stop_price` a market order is placed.
+    /// <br>
+    /// Use `OrderStop::Loss` with `OrderSide::Sell`, to sell when the price falls to `stop_price`,
+    /// and `OrderStop::Entry` with `OrderSide::Buy`, to buy when the price rises to `stop_price`.
+    /// Placing an order with the other combinations returns an error of kind `ErrorKind::InvalidParameter` without sending a request.
+    pub fn stop_market(
+        side: OrderSide,
+        product_id: impl Into<ProductId>,
+        size_or_funds: SizeOrFunds,
+        stop_price: &str,
+        stop: OrderStop,
+    ) -> impl SharedOptions {
+        Self {
+            r#type: "market".to_string(),
+            size: match size_or_funds {
+                SizeOrFunds::Size(ref n) => Some(n.to_owned()),
+                _ => None,
+            },
+            price: None,
+            side,
+            client_oid: None,
+            self_trade_prevention: None,
+            time_in_force: None,
+            cancel_after: None,
+            post_only: None,
+            funds: match size_or_funds {
+                SizeOrFunds::Funds(ref n) => Some(n.to_owned()),
+                _ => None,
+            },
+            product_id: product_id.into().to_string(),
             stop: Some(stop),
             stop_price: Some(stop_price.to_owned()),
+            profile_id: None,
         }
     }
 }
 
 /// 'SharedOptions' options can be used with market, limit and stop order types
+#[must_use = "a builder does nothing until it is built"]
 pub trait SharedOptions {
     fn self_trade_prevention(self, self_trade_prevention: SelfTradePrevention) -> Self;
     fn client_oid(self, client_oid: String) -> Self;
+    fn profile_id(self, profile_id: &str) -> Self;
+    #[cfg(feature = "full")]
+    fn round_to_product(self, product: &Product) -> Self;
     fn build(self) -> Order;
 }
 
@@ -235,6 +378,29 @@ impl SharedOptions for OrderBuilder {
         self
     }
 
+    /// Sets the profile to place the Order on
+    /// <br>
+    /// By default, orders are placed on the profile of the API key
+    fn profile_id(mut self, profile_id: &str) -> Self {
+        self.profile_id = Some(profile_id.to_string());
+        self
+    }
+
+    /// Rounds the size down to the `base_increment` of `product`, and the price, stop price and funds down to its `quote_increment`
+    /// <br>
+    /// Coinbase rejects orders with more decimals than the increments allow. Amounts which aren't valid decimals are left unchanged
+    #[cfg(feature = "full")]
+    fn round_to_product(mut self, product: &Product) -> Self {
+        let round = |amount: Option<String>, increment: &str| {
+            amount.map(|n| round_to_increment(&n, increment).unwrap_or(n))
+        };
+        self.size = round(self.size, &product.base_increment);
+        self.price = round(self.price, &product.quote_increment);
+        self.stop_price = round(self.stop_price, &product.quote_increment);
+        self.funds = round(self.funds, &product.quote_increment);
+        self
+    }
+
     /// Builds `Order`
     fn build(self) -> Order {
         Order {
@@ -249,14 +415,15 @@ impl SharedOptions for OrderBuilder {
             post_only: self.post_only,
             funds: self.funds,
             product_id: self.product_id,
-            stp: self.stp,
             stop: self.stop,
             stop_price: self.stop_price,
+            profile_id: self.profile_id,
         }
     }
 }
 
 /// Builder options for Limit Orders
+#[must_use = "a builder does nothing until it is built"]
 pub trait LimitOptions {
     fn time_in_force(self, time_in_force: TimeInForce) -> Self;
 }
@@ -281,11 +448,39 @@ impl LimitOptions for OrderBuilder {
 }
 
 /// Buy or Sell `Order`
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OrderSide {
     Buy,
     Sell,
 }
+
+/// Parses `buy` or `sell`, as coinbase returns the side of an order
+/// <br>
+/// ~~~~
+/// let side: OrderSide = "buy".parse().unwrap();
+/// assert_eq!(side, OrderSide::Buy);
+/// ~~~~
+impl FromStr for OrderSide {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "buy" => Ok(Self::Buy),
+            "sell" => Ok(Self::Sell),
+            _ => Err(unrecognized("order side", s)),
+        }
+    }
+}
+
+impl TryFrom<&str> for OrderSide {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// Loss triggers when the last trade price changes to a value at or below the stop_price.
 /// <br>
 /// Entry triggers when the last trade price changes to a value at or above the stop_price.
@@ -296,6 +491,8 @@ pub enum OrderStop {
 }
 
 /// Size or Funds of Currency
+/// <br>
+/// Coinbase rejects market orders with both a size and funds, taking one or the other means an `Order` can't have both
 #[derive(Clone, Debug)]
 pub enum SizeOrFunds {
     Size(String),
@@ -316,6 +513,33 @@ pub enum TimeInForce {
     FillOrKill,
 }
 
+/// Parses `GTC`, `IOC` or `FOK`, as coinbase returns the time in force of an order
+/// <br>
+/// `GTC` parses to `GoodTillCancel` with `post_only` of `false`, coinbase returns `post_only` as a separate field. `GTT` is an error since the time in force string doesn't include the `CancelAfter` it requires
+impl FromStr for TimeInForce {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GTC" => Ok(Self::GoodTillCancel { post_only: false }),
+            "IOC" => Ok(Self::ImmediateOrCancel),
+            "FOK" => Ok(Self::FillOrKill),
+            "GTT" => Err(Error::new(ErrorKind::InvalidParameter(
+                "time in force \"GTT\" can't be parsed without its cancel after".to_string(),
+            ))),
+            _ => Err(unrecognized("time in force", s)),
+        }
+    }
+}
+
+impl TryFrom<&str> for TimeInForce {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum CancelAfter {
     Minute,
@@ -411,3 +635,34 @@ impl serde::Serialize for SizeOrFunds {
         }
     }
 }
+
+// the error of parsing a string which isn't one of the values of an enum
+pub(super) fn unrecognized(name: &str, s: &str) -> Error {
+    Error::new(ErrorKind::InvalidParameter(format!(
+        "unrecognized {} {:?}",
+        name, s
+    )))
+}
+
+#[cfg(all(test, feature = "full"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_stop() {
+        let order = |side, stop| {
+            OrderBuilder::stop_limit(side, "BTC-USD", "29000.00", "0.01", "30000.00", stop).build()
+        };
+        assert!(order(OrderSide::Sell, OrderStop::Loss).check_stop().is_ok());
+        assert!(order(OrderSide::Buy, OrderStop::Entry).check_stop().is_ok());
+        for (side, stop) in &[
+            (OrderSide::Buy, OrderStop::Loss),
+            (OrderSide::Sell, OrderStop::Entry),
+        ] {
+            let error = order(*side, *stop).check_stop().unwrap_err();
+            assert!(matches!(error.kind, ErrorKind::InvalidParameter(_)));
+        }
+        let limit = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "29000.00", "0.01").build();
+        assert!(limit.check_stop().is_ok());
+    }
+}
@@ -1,30 +1,42 @@
-use crate::configure_pagination;
 use crate::{
-    deserialize_option_to_date, deserialize_response, deserialize_to_date, Json, COINBASE_API_URL,
-    COINBASE_SANDBOX_API_URL,
+    configure_pagination,
+    increment::{check_amount, is_sum},
+    parse_amount, MAX_PAGINATION_LIMIT,
 };
+use crate::{deserialize_option_to_date, deserialize_to_date, Json};
 
+use super::order::unrecognized;
 use super::Order;
+use super::OrderSide;
+use super::PrivateClient;
 use super::Report;
+use super::ReportType;
+use crate::public_client::{Currency, Ticker};
 
-use crate::error::{Error, ErrorKind, ErrorMessage, StatusError};
-use base64;
+use crate::error::{Error, ErrorKind, StatusError};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use core::f64;
-use crypto::{self, mac::Mac};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use reqwest;
-use serde::{self, Deserialize};
-use std::str;
-use std::time::{SystemTime, SystemTimeError};
-
-/// `PrivateClient` requires authentication and provide access to placing orders and other account information
-pub struct PrivateClient {
-    reqwest_client: reqwest::Client,
-    secret: String,
-    passphrase: String,
-    key: String,
-    url: &'static str,
-}
+use serde::{self, Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::{self, FromStr};
+use std::time::Duration;
+
+// interval between requests of methods which poll until a condition is met
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// the most ledger requests `get_full_account_snapshot` sends at once
+const LEDGER_REQUESTS: usize = 4;
+
+// the most ticker requests `portfolio_value` sends at once
+const TICKER_REQUESTS: usize = 4;
+
+// the most fill requests `get_recent_fills` sends at once
+const FILL_REQUESTS: usize = 4;
 
 impl PrivateClient {
     async fn get_paginated<T>(
@@ -37,179 +49,44 @@ impl PrivateClient {
     where
         T: serde::de::DeserializeOwned,
     {
-        let pagination_params = configure_pagination(before, after, limit);
+        let pagination_params = configure_pagination(before, after, limit)?;
         self.get(&format!("{}{}", path, pagination_params)).await
     }
 
-    async fn get<T>(&self, path: &str) -> Result<T, Error>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let headers = self.access_headers(path, None, "GET");
-        let response = self
-            .reqwest_client
-            .get(format!("{}{}", self.url, path))
-            .headers(headers)
-            .send()
-            .await?;
-        deserialize_response::<T>(response).await
-    }
-
-    async fn post_and_deserialize<T, K>(&self, path: &str, body: Option<K>) -> Result<T, Error>
-    where
-        K: serde::Serialize,
-        T: serde::de::DeserializeOwned,
-    {
-        deserialize_response::<T>(self.post(path, body).await?).await
-    }
-
-    async fn post<K>(&self, path: &str, body: Option<K>) -> Result<reqwest::Response, Error>
-    where
-        K: serde::Serialize,
-    {
-        let request_builder = self.reqwest_client.post(format!("{}{}", self.url, path));
-        Ok(if let Some(n) = body {
-            request_builder
-                .headers(self.access_headers(path, Some(&serde_json::to_string(&n)?), "POST"))
-                .json::<K>(&n)
-                .send()
-        } else {
-            request_builder
-                .headers(self.access_headers(path, None, "POST"))
-                .send()
-        }
-        .await?)
-    }
-
-    async fn delete<T>(&self, path: &str) -> Result<T, Error>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let headers = self.access_headers(path, None, "DELETE");
-        let response = self
-            .reqwest_client
-            .delete(format!("{}{}", self.url, path))
-            .headers(headers)
-            .send()
-            .await?;
-        deserialize_response::<T>(response).await
-    }
-
-    fn get_current_timestamp() -> Result<String, SystemTimeError> {
-        Ok(SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)?
-            .as_secs()
-            .to_string())
-    }
-
-    fn access_headers(
-        &self,
-        url: &str,
-        body: Option<&str>,
-        method: &str,
-    ) -> reqwest::header::HeaderMap {
-        let timestamp = PrivateClient::get_current_timestamp().unwrap();
-        let signature = self.sign_message(url, body, &timestamp, method);
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            reqwest::header::USER_AGENT,
-            reqwest::header::HeaderValue::from_str("coinbase-client")
-                .expect("invalid user agent value"),
-        );
-        headers.insert(
-            reqwest::header::HeaderName::from_static("cb-access-key"),
-            reqwest::header::HeaderValue::from_str(&self.key)
-                .expect("invalid user cb-access-key value"),
-        );
-        headers.insert(
-            reqwest::header::HeaderName::from_static("cb-access-sign"),
-            reqwest::header::HeaderValue::from_str(&signature)
-                .expect("invalid cb-access-sign value"),
-        );
-        headers.insert(
-            reqwest::header::HeaderName::from_static("cb-access-timestamp"),
-            reqwest::header::HeaderValue::from_str(&timestamp)
-                .expect("invalid user cb-access-timestamp value"),
-        );
-        headers.insert(
-            reqwest::header::HeaderName::from_static("cb-access-passphrase"),
-            reqwest::header::HeaderValue::from_str(&self.passphrase)
-                .expect("invalid user cb-access-passphrase value"),
-        );
-
-        headers
-    }
-
-    fn sign_message(&self, url: &str, body: Option<&str>, timestamp: &str, method: &str) -> String {
-        let mut prehash = String::new();
-        // omit body if not supplied
-        match body {
-            Some(body) => {
-                prehash.push_str(&timestamp);
-                prehash.push_str(&method);
-                prehash.push_str(&url);
-                prehash.push_str(&body);
-            }
-            None => {
-                prehash.push_str(&timestamp);
-                prehash.push_str(&method);
-                prehash.push_str(&url);
-            }
-        }
-        // decode your coinbase api secret
-        let decoded_secret = base64::decode(&self.secret)
-            .expect("unable to decode secret, is your secret in base 64 encoding");
-        // hmac-sha256 it
-        let mut hmac = crypto::hmac::Hmac::new(crypto::sha2::Sha256::new(), &decoded_secret);
-        hmac.input(prehash.as_bytes());
-        let hmac_result = hmac.result();
-        let hmac_code = hmac_result.code();
-        let base64_encoding = base64::encode(hmac_code);
-        // return base64 encoded hmac result
-        base64_encoding
-    }
-
-    /// Creates a new `PrivateClient`
+    /// Gets a list of trading accounts from the profile of the API key.
     /// <br>
-    /// ~~~~
-    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// ~~~~
-    pub fn new(secret: String, passphrase: String, key: String) -> Self {
-        Self {
-            reqwest_client: reqwest::Client::new(),
-            secret, // shared secret
-            key,
-            passphrase,
-            url: COINBASE_API_URL,
-        }
-    }
-
-    /// Creates a new `PrivateClient` for testing API connectivity and web trading
+    /// [API docs](https://docs.pro.coinbase.com/#account)
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let accounts = client.get_accounts().await.unwrap();
     /// ~~~~
-    pub fn new_sandbox(secret: String, passphrase: String, key: String) -> Self {
-        Self {
-            reqwest_client: reqwest::Client::new(),
-            secret,
-            key,
-            passphrase,
-            url: COINBASE_SANDBOX_API_URL,
-        }
+    pub async fn get_accounts(&self) -> Result<Vec<Account>, Error> {
+        let accounts = self.get_cached("/accounts").await?;
+        Ok(accounts)
     }
 
-    /// Gets a list of trading accounts from the profile of the API key.
+    /// Get the trading accounts of a profile which have a nonzero balance
+    /// <br>
+    /// Built on `get_accounts`, so only the accounts of the API key's profile are available. Accounts of other profiles require an API key of that profile
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#account)
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// let accounts = client.get_accounts().await.unwrap();
+    /// let balances = client
+    ///     .get_profile_balances("e1d7731f-b7e2-4285-b711-eeec76fc2aff")
+    ///     .await
+    ///     .unwrap();
     /// ~~~~
-    pub async fn get_accounts(&self) -> Result<Vec<Account>, Error> {
-        let accounts = self.get("/accounts").await?;
-        Ok(accounts)
+    pub async fn get_profile_balances(&self, profile_id: &str) -> Result<Vec<Account>, Error> {
+        let mut balances = Vec::new();
+        for account in self.get_accounts().await? {
+            if account.profile_id == profile_id && parse_amount(&account.balance)? > 0.0 {
+                balances.push(account);
+            }
+        }
+        Ok(balances)
     }
 
     /// Get trading account by account ID
@@ -227,6 +104,30 @@ impl PrivateClient {
         Ok(account)
     }
 
+    /// Get trading account by account ID together with its currency
+    /// <br>
+    /// The `Currency` has the precision of the account's balance, see `Currency::decimals`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#account)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let (account, currency) = client
+    ///     .get_account_with_currency("1f6a7175-a89c-494f-986d-af9987e6dd69")
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_account_with_currency(
+        &self,
+        account_id: &str,
+    ) -> Result<(Account, Currency), Error> {
+        let account = self.get_account(account_id).await?;
+        let currency = self
+            .get(&format!("/currencies/{}", account.currency))
+            .await?;
+        Ok((account, currency))
+    }
+
     /// Get account activity of the API key's profile.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#get-account-history)
@@ -261,6 +162,82 @@ impl PrivateClient {
         Ok(account)
     }
 
+    /// Get every trading account of the API key's profile together with its recent account history
+    /// <br>
+    /// Fetches the accounts with `get_accounts`, then the most recent 100 entries of each account's ledger with `get_account_history`, a few accounts at a time. Requests wait on the client's rate limit if one is set with `PrivateClientBuilder::rate_limit`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-account-history)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// for (account, history) in client.get_full_account_snapshot().await.unwrap() {
+    ///     println!("{} {}, {} ledger entries", account.balance, account.currency, history.len());
+    /// }
+    /// ~~~~
+    pub async fn get_full_account_snapshot(
+        &self,
+    ) -> Result<Vec<(Account, Vec<AccountHistory>)>, Error> {
+        let accounts = self.get_accounts().await?;
+        stream::iter(accounts)
+            .map(|account| async move {
+                let history = self
+                    .get_account_history(&account.id, None, None, None)
+                    .await?;
+                Ok((account, history))
+            })
+            .buffered(LEDGER_REQUESTS)
+            .try_collect()
+            .await
+    }
+
+    /// Get the total value of all accounts in `quote_currency`
+    /// <br>
+    /// Fetches the accounts with `get_accounts`, then the ticker of the `{currency}-{quote_currency}` product of each currency with a balance, a few at a time, and sums each balance at the ticker's last trade price. Balances of `quote_currency` count at their face value. Currencies with no product against `quote_currency` are left out of the value and listed in `PortfolioValue::unpriced`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-product-ticker)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let portfolio = client.portfolio_value("USD").await.unwrap();
+    /// println!("{} USD, not priced: {:?}", portfolio.value, portfolio.unpriced);
+    /// ~~~~
+    pub async fn portfolio_value(&self, quote_currency: &str) -> Result<PortfolioValue, Error> {
+        let accounts = self.get_accounts().await?;
+        let mut portfolio = PortfolioValue::default();
+        let mut priced = Vec::new();
+        for account in accounts {
+            let balance = parse_amount(&account.balance)?;
+            if balance == 0.0 {
+                continue;
+            }
+            if account.currency == quote_currency {
+                portfolio.value += balance;
+            } else {
+                priced.push((account.currency, balance));
+            }
+        }
+        let prices: Vec<_> = stream::iter(priced)
+            .map(|(currency, balance)| async move {
+                let ticker = self
+                    .get::<Ticker>(&format!("/products/{}-{}/ticker", currency, quote_currency))
+                    .await;
+                (currency, balance, ticker)
+            })
+            .buffered(TICKER_REQUESTS)
+            .collect()
+            .await;
+        for (currency, balance, ticker) in prices {
+            match ticker {
+                Ok(ticker) => portfolio.value += balance * parse_amount(&ticker.price)?,
+                Err(Error {
+                    kind: ErrorKind::Status(StatusError { code: 404, .. }),
+                }) => portfolio.unpriced.push(currency),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(portfolio)
+    }
+
     /// Get holds of an account that belong to the same profile as the API key.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#get-holds)\
@@ -295,6 +272,72 @@ impl PrivateClient {
         Ok(account)
     }
 
+    /// Get the order or transfer which placed a hold, e.g. to explain why funds of an account aren't available
+    /// <br>
+    /// A hold of type `order` is resolved with `get_order` and a hold of type `transfer` by fetching the transfer. An order which is done no longer holds funds, so resolving a stale hold may return a 404
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-holds)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let holds = client
+    ///     .get_account_holds("680f85f4-1a99-4108-93ce-a9066f9de246", None, None, None)
+    ///     .await
+    ///     .unwrap();
+    /// for hold in &holds {
+    ///     match client.resolve_hold(hold).await.unwrap() {
+    ///         HoldResolution::Order(order) => println!("order {} holds {}", order.id, hold.amount),
+    ///         HoldResolution::Transfer(transfer) => println!("transfer {} holds {}", transfer.id, hold.amount),
+    ///     }
+    /// }
+    /// ~~~~
+    pub async fn resolve_hold(&self, hold: &Hold) -> Result<HoldResolution, Error> {
+        match hold.r#type {
+            HoldType::Order => Ok(HoldResolution::Order(Box::new(
+                self.get_order(&hold.r#ref).await?,
+            ))),
+            HoldType::Transfer => Ok(HoldResolution::Transfer(
+                self.get(&format!("/transfers/{}", hold.r#ref)).await?,
+            )),
+        }
+    }
+
+    /// Get the deposits and withdrawals of an account, in descending order by created time
+    /// <br>
+    /// Unlike `get_deposits` and `get_withdrawals`, which list the transfers of a whole profile, only transfers to and from this account are returned
+    /// <br>
+    /// This request is [paginated](https://docs.pro.coinbase.com/#pagination)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let transfers = client
+    /// .get_account_transfers(
+    ///     "680f85f4-1a99-4108-93ce-a9066f9de246",
+    ///     None,
+    ///     None,
+    ///     Some(100),
+    /// )
+    /// .await
+    /// .unwrap();
+    /// ~~~~
+    pub async fn get_account_transfers(
+        &self,
+        account_id: &str,
+        before: Option<&str>,
+        after: Option<&str>,
+        limit: Option<u16>,
+    ) -> Result<Vec<Transfer>, Error> {
+        let transfers = self
+            .get_paginated(
+                &format!("/accounts/{}/transfers?", account_id),
+                before,
+                after,
+                limit,
+            )
+            .await?;
+        Ok(transfers)
+    }
+
     /// You can place three types of orders: limit, market and stop
     /// <br>
     /// [Overview of order types and settings](https://help.coinbase.com/en/pro/trading-and-funding/orders/overview-of-order-types-and-settings-stop-limit-market)
@@ -308,17 +351,66 @@ impl PrivateClient {
     /// .build();
     /// let res = client.place_order(order).await.unwrap();
     /// ~~~~
+    #[must_use = "the order may not have been placed, check the result"]
     pub async fn place_order(&self, order: Order) -> Result<String, Error> {
         #[derive(Deserialize, Debug)]
         pub struct OrderID {
             pub id: String,
         }
+        order.check_stop()?;
         Ok(self
             .post_and_deserialize::<OrderID, _>("/orders", Some(order))
             .await?
             .id)
     }
 
+    /// Place an order, returning the order as acknowledged by coinbase rather than only its id
+    /// <br>
+    /// The acknowledgment has the status, creation time and fills so far of the order, so it needn't be fetched again with `get_order` after placing it
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#place-a-new-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0")
+    /// .build();
+    /// let order = client.place_order_full(order).await.unwrap();
+    /// println!("{} {}", order.id, order.status);
+    /// ~~~~
+    #[must_use = "the order may not have been placed, check the result"]
+    pub async fn place_order_full(&self, order: Order) -> Result<OrderInfo, Error> {
+        order.check_stop()?;
+        self.post_and_deserialize("/orders", Some(order)).await
+    }
+
+    /// Place several orders, sending at most `max_concurrency` requests at once
+    /// <br>
+    /// Returns the order ID, or the error placing the order, of each order in the same order as `orders`. A failed order doesn't stop the others from being placed
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#place-a-new-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let orders = vec![
+    ///     OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0").build(),
+    ///     OrderBuilder::limit(OrderSide::Buy, "ETH-USD", "2000.0", "1.0").build(),
+    /// ];
+    /// let order_ids = client.place_orders(orders, 2).await;
+    /// ~~~~
+    #[must_use = "some orders may not have been placed, check the results"]
+    pub async fn place_orders(
+        &self,
+        orders: Vec<Order>,
+        max_concurrency: usize,
+    ) -> Vec<Result<String, Error>> {
+        stream::iter(orders)
+            .map(|order| self.place_order(order))
+            // buffered keeps the results in the order of the orders
+            .buffered(max_concurrency.max(1))
+            .collect()
+            .await
+    }
+
     /// Cancel order specified by order ID
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#cancel-an-order)
@@ -331,17 +423,59 @@ impl PrivateClient {
     /// let canceled_order_id = client.cancel_order(&order_to_cancel_id)
     /// .await.unwrap();
     /// ~~~~
+    #[must_use = "the order may not have been cancelled, check the result"]
     pub async fn cancel_order(&self, order_id: &str) -> Result<String, Error> {
         Ok(self.delete(&format!("/orders/{}", order_id)).await?)
     }
 
+    /// Cancel order specified by order ID, distinguishing an order which was already done from a cancelled order
+    /// <br>
+    /// Coinbase rejects cancelling an order which has been filled or cancelled with a 400 "Order already done" or, once the order is gone, a 404. Unlike `cancel_order` these are returned as `Ok(CancelOutcome::AlreadyDone)` rather than an error
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#cancel-an-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let outcome = client
+    ///     .cancel_order_checked("4f2756cf-dcb5-492b-83e5-5f2141892758")
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn cancel_order_checked(&self, order_id: &str) -> Result<CancelOutcome, Error> {
+        cancel_outcome(self.cancel_order(order_id).await)
+    }
+
     /// Cancel order specified by order OID
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#cancel-an-order)
+    #[must_use = "the order may not have been cancelled, check the result"]
     pub async fn cancel_order_by_oid(&self, oid: &str) -> Result<String, Error> {
         Ok(self.delete(&format!("/orders/client:{}", oid)).await?)
     }
 
+    /// Cancel order specified by order OID, distinguishing an order which was already done or doesn't exist from a cancelled order
+    /// <br>
+    /// The OID must be the UUID passed as `client_oid` when placing the order, anything else returns an error of kind `ErrorKind::InvalidParameter` without sending a request. Coinbase only retains client OIDs for a limited time after the order is done, cancelling by a stale OID returns `Ok(CancelOutcome::AlreadyDone)` like an order which doesn't exist
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#cancel-an-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let outcome = client
+    ///     .cancel_order_by_oid_checked("2d6bd23a-f2a9-4e5b-8ff8-2a5d3bd4e3b9")
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn cancel_order_by_oid_checked(&self, oid: &str) -> Result<CancelOutcome, Error> {
+        if uuid::Uuid::parse_str(oid).is_err() {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "client order id must be a UUID, got \"{}\"",
+                oid
+            ))));
+        }
+        cancel_outcome(self.cancel_order_by_oid(oid).await)
+    }
+
     /// Cancel all orders
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#cancel-an-order)
@@ -350,10 +484,69 @@ impl PrivateClient {
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
     /// let canceled_orders_ids = client.cancel_orders().await.unwrap();
     /// ~~~~
+    #[must_use = "the orders may not have been cancelled, check the result"]
     pub async fn cancel_orders(&self) -> Result<Vec<String>, Error> {
         Ok(self.delete("/orders").await?)
     }
 
+    /// Cancel all orders, reporting which of the orders open before cancelling weren't cancelled
+    /// <br>
+    /// Coinbase only returns the ids of the cancelled orders, so the open, active and pending orders are fetched with `get_orders` before cancelling. Orders among them which coinbase didn't cancel are returned in `not_cancelled`. At most 100 orders are fetched, orders beyond those are only reported if they were cancelled
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#cancel-all)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let result = client.cancel_orders_checked().await.unwrap();
+    /// assert!(result.not_cancelled.is_empty());
+    /// ~~~~
+    #[must_use = "the orders may not have been cancelled, check the result"]
+    pub async fn cancel_orders_checked(&self) -> Result<CancelAllResult, Error> {
+        let open = self
+            .get_orders(Some(OrderStatus::OpenActivePending), None, None, None)
+            .await?;
+        let cancelled = self.cancel_orders().await?;
+        let not_cancelled = open
+            .into_iter()
+            .map(|order| order.id)
+            .filter(|id| !cancelled.contains(id))
+            .collect();
+        Ok(CancelAllResult {
+            cancelled,
+            not_cancelled,
+        })
+    }
+
+    /// Cancel all orders and wait until no open, active or pending orders remain
+    /// <br>
+    /// Cancellation is asynchronous on Coinbase's side, this polls `get_orders` until it returns no orders. Returns an error of kind `ErrorKind::Timeout` if orders remain after `timeout`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#cancel-all)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// client
+    ///     .cancel_all_and_wait(Duration::from_secs(10))
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn cancel_all_and_wait(&self, timeout: Duration) -> Result<(), Error> {
+        self.cancel_orders().await?;
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let orders = self
+                .get_orders(Some(OrderStatus::OpenActivePending), None, None, None)
+                .await?;
+            if orders.is_empty() {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::new(ErrorKind::Timeout));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
     /// Get open orders from the profile that the API key belongs
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-orders)
@@ -380,24 +573,50 @@ impl PrivateClient {
         limit: Option<u16>,
     ) -> Result<Vec<OrderInfo>, Error> {
         let path = match order_status {
-            Some(n) => {
-                let params = match n {
-                    OrderStatus::Open => "status=open",
-                    OrderStatus::Active => "status=active",
-                    OrderStatus::Pending => "status=pending",
-                    OrderStatus::OpenActive => "status=open&status=active",
-                    OrderStatus::OpenPending => "status=open&status=pending",
-                    OrderStatus::ActivePending => "status=active&status=pending",
-                    OrderStatus::OpenActivePending => "status=open&status=active&status=pending",
-                };
-                format!("/orders?{}&", params)
-            }
+            Some(n) => format!("/orders?{}&", n.params()),
             None => String::from("/orders?"),
         };
 
         Ok(self.get_paginated(&path, before, after, limit).await?)
     }
 
+    /// Get orders from the profile that the API key belongs, sorted by a field
+    /// <br>
+    /// Use it with a limit to get only the newest few orders rather than every open order, e.g. the 20 most recent orders by `OrderSortedBy::CreatedAt` and `Sorting::Desc`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#list-orders)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let orders = client
+    ///     .get_orders_sorted(
+    ///         Some(OrderStatus::OpenActivePending),
+    ///         OrderSortedBy::CreatedAt,
+    ///         Sorting::Desc,
+    ///         Some(20),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_orders_sorted(
+        &self,
+        order_status: Option<OrderStatus>,
+        sorted_by: OrderSortedBy,
+        sorting: Sorting,
+        limit: Option<u16>,
+    ) -> Result<Vec<OrderInfo>, Error> {
+        let mut path = format!(
+            "/orders?sortedBy={}&sorting={}&",
+            sorted_by.as_str(),
+            sorting.as_str()
+        );
+        if let Some(n) = order_status {
+            path.push_str(&format!("{}&", n.params()));
+        }
+
+        self.get_paginated(&path, None, None, limit).await
+    }
+
     /// Get open order from the profile that the API key belongs
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#get-an-order)
@@ -413,6 +632,95 @@ impl PrivateClient {
         Ok(self.get(&format!("/orders/{}", order_id)).await?)
     }
 
+    /// Get order specified by order ID, or `None` if the order doesn't exist
+    /// <br>
+    /// Unlike `get_order`, a 404 response is returned as `Ok(None)` rather than an error
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-an-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let order = client
+    ///     .try_get_order("4f2756cf-dcb5-492b-83e5-5f2141892758")
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn try_get_order(&self, order_id: &str) -> Result<Option<OrderInfo>, Error> {
+        match self.get_order(order_id).await {
+            Ok(order) => Ok(Some(order)),
+            Err(Error {
+                kind: ErrorKind::Status(StatusError { code: 404, .. }),
+            }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get order specified by order ID together with its fills
+    /// <br>
+    /// The order and its fills are requested concurrently. Returns an error if the order can't be retrieved, e.g. a 404 for an order which doesn't exist, but a 404 for the fills returns the order with no fills. At most 100 fills are returned, use `get_fill_by_order_id` to page through more
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-an-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let (order, fills) = client
+    ///     .get_order_with_fills("4f2756cf-dcb5-492b-83e5-5f2141892758")
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_order_with_fills(
+        &self,
+        order_id: &str,
+    ) -> Result<(OrderInfo, Vec<Fill>), Error> {
+        let (order, fills) = futures::join!(
+            self.get_order(order_id),
+            self.get_fill_by_order_id(order_id, None, None, None)
+        );
+        let order = order?;
+        let fills = match fills {
+            Ok(fills) => fills,
+            Err(Error {
+                kind: ErrorKind::Status(StatusError { code: 404, .. }),
+            }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok((order, fills))
+    }
+
+    /// Get order specified by order ID once it has settled
+    /// <br>
+    /// The size of a market order placed with funds, and the filled size and executed value of any order, are only final once the order has settled. This polls `get_order` until `settled` is `true`. Returns an error of kind `ErrorKind::Timeout` if the order hasn't settled after `timeout`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-an-order)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let order = OrderBuilder::market(OrderSide::Buy, "BTC-USD", SizeOrFunds::Funds("10.00".to_string()))
+    /// .build();
+    /// let order_id = client.place_order(order).await.unwrap();
+    /// let order = client
+    ///     .get_order_and_wait_settled(&order_id, Duration::from_secs(10))
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_order_and_wait_settled(
+        &self,
+        order_id: &str,
+        timeout: Duration,
+    ) -> Result<OrderInfo, Error> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let order = self.get_order(order_id).await?;
+            if order.settled {
+                return Ok(order);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::new(ErrorKind::Timeout));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
     /// Gets order specified by order OID
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#get-an-order)
@@ -476,6 +784,98 @@ impl PrivateClient {
             .await?)
     }
 
+    /// Get all fills by specified product_id of the API key's profile, most recent first
+    /// <br>
+    /// Requests pages of 100 fills one after another, following the `CB-AFTER` cursor of each page until there are no older fills. Requests wait on the client's rate limit if one is set with `PrivateClientBuilder::rate_limit`. Returns an error of kind `ErrorKind::TooManyPages` rather than a partial history if there are more than `max_pages` pages of fills, or of kind `ErrorKind::InvalidParameter` if `max_pages` is zero
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#fills)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let fills = client
+    ///     .get_all_fills_for_product("BTC-USD", 50)
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_all_fills_for_product(
+        &self,
+        product_id: &str,
+        max_pages: usize,
+    ) -> Result<Vec<Fill>, Error> {
+        if max_pages == 0 {
+            return Err(Error::new(ErrorKind::InvalidParameter(
+                "max_pages must be greater than zero".to_string(),
+            )));
+        }
+        let mut fills = Vec::new();
+        let mut after = None;
+        // pages which had fills, the empty page ending the history isn't counted
+        let mut pages = 0;
+        loop {
+            let pagination_params =
+                configure_pagination(None, after.as_deref(), Some(MAX_PAGINATION_LIMIT))?;
+            let (page, next): (Vec<Fill>, _) = self
+                .get_page(&format!(
+                    "/fills?product_id={}&{}",
+                    product_id, pagination_params
+                ))
+                .await?;
+            if page.is_empty() {
+                return Ok(fills);
+            }
+            if pages == max_pages {
+                return Err(Error::new(ErrorKind::TooManyPages(max_pages)));
+            }
+            pages += 1;
+            fills.extend(page);
+            match next {
+                Some(n) => after = Some(n),
+                None => return Ok(fills),
+            }
+        }
+    }
+
+    /// Get recent fills of several products of the API key's profile, most recent first
+    /// <br>
+    /// Coinbase doesn't support querying fills without an order_id or product_id, so this requests the recent fills of each product with `get_fills_by_product_id`, a few at a time, and merges them sorted by `created_at`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#fills)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let fills = client
+    ///     .get_recent_fills(&["BTC-USD", "ETH-USD"])
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_recent_fills(&self, product_ids: &[&str]) -> Result<Vec<Fill>, Error> {
+        let fills: Vec<Vec<Fill>> = stream::iter(product_ids)
+            .map(|product_id| self.get_fills_by_product_id(product_id, None, None, None))
+            .buffer_unordered(FILL_REQUESTS)
+            .try_collect()
+            .await?;
+        let mut fills: Vec<Fill> = fills.into_iter().flatten().collect();
+        fills.sort_by_key(|fill| Reverse(fill.created_at));
+        Ok(fills)
+    }
+
+    /// Get statistics of recent fills by specified product_id of the API key's profile
+    /// <br>
+    /// Aggregates maker and taker counts, total fees paid, and buy and sell volume of the fills returned by `get_fills_by_product_id`
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#fills)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let stats = client.get_product_fill_stats("BTC-USD").await.unwrap();
+    /// ~~~~
+    pub async fn get_product_fill_stats(&self, product_id: &str) -> Result<FillStats, Error> {
+        let fills = self
+            .get_fills_by_product_id(product_id, None, None, None)
+            .await?;
+        FillStats::from_fills(&fills)
+    }
+
     /// Get information on your payment method transfer limits, as well as buy/sell limits per currency
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#limits)
@@ -488,6 +888,39 @@ impl PrivateClient {
         Ok(self.get(&format!("/users/self/exchange-limits")).await?)
     }
 
+    /// Check whether an amount of a currency can be withdrawn
+    /// <br>
+    /// Returns `true` if the amount is within the available balance of the currency's account and the remaining withdrawal limit of the currency. The accounts are always fetched, never read from the cache set with `PrivateClientBuilder::cache_ttl`, so the available balance is current
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#limits)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let can_withdraw = client.can_withdraw("ADA", "6.0").await.unwrap();
+    /// ~~~~
+    pub async fn can_withdraw(&self, currency: &str, amount: &str) -> Result<bool, Error> {
+        check_amount(amount)?;
+        let amount = parse_amount(amount)?;
+        let accounts: Vec<Account> = self.get("/accounts").await?;
+        let account = match accounts.iter().find(|n| n.currency == currency) {
+            Some(account) => account,
+            None => return Ok(false),
+        };
+        if amount > parse_amount(&account.available)? {
+            return Ok(false);
+        }
+        let limits = self.get_limits().await?;
+        // currencies without a withdrawal limit are only bounded by the available balance
+        if let Some(remaining) =
+            limits["transfer_limits"]["exchange_withdraw"][currency]["remaining"].as_str()
+        {
+            if amount > parse_amount(remaining)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     /// Get deposits from the profile of the API key, in descending order by created time
     /// <br>
     /// **optional parameters**
@@ -498,7 +931,7 @@ impl PrivateClient {
     /// <br>
     /// *after*: if after is set, then it returns deposits created before the after timestamp, sorted by newest
     /// <br>
-    /// *limit*: truncate list to this many deposits, capped at 100. Default is 100.
+    /// *limit*: truncate list to this many deposits, at most 100, a greater limit returns an error of kind `ErrorKind::InvalidParameter`. Default is 100.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-deposits)
     /// <br>
@@ -538,7 +971,7 @@ impl PrivateClient {
     /// <br>
     /// *after*: if after is set, then it returns internal deposits created before the after timestamp, sorted by newest
     /// <br>
-    /// *limit*: truncate list to this many internal deposits, capped at 100. Default is 100.
+    /// *limit*: truncate list to this many internal deposits, at most 100, a greater limit returns an error of kind `ErrorKind::InvalidParameter`. Default is 100.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-deposits)
     /// <br>
@@ -602,7 +1035,7 @@ impl PrivateClient {
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
     /// let res = client
-    /// .deposit_funds(10.00, "USD", "1b4b4fbc-8921-5e7c-b362-a1c589a2cf20")
+    /// .deposit_funds("10.00", "USD", "1b4b4fbc-8921-5e7c-b362-a1c589a2cf20")
     /// .await
     /// .unwrap();
     /// ~~~~
@@ -612,6 +1045,7 @@ impl PrivateClient {
         currency: &str,
         payment_method_id: &str,
     ) -> Result<DepositInfo, Error> {
+        check_amount(amount)?;
         Ok(self
             .post_and_deserialize(
                 "/deposits/payment-method",
@@ -631,16 +1065,17 @@ impl PrivateClient {
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
     /// let res = client
-    ///     .deposit_funds_from_coinbase(10.00, "BTC", "95671473-4dda-5264-a654-fc6923e8a334")
+    ///     .deposit_funds_from_coinbase("10.00", "BTC", "95671473-4dda-5264-a654-fc6923e8a334")
     ///     .await
     ///     .unwrap();
     /// ~~~~
     pub async fn deposit_funds_from_coinbase(
         &self,
-        amount: f64,
+        amount: &str,
         currency: &str,
         coinbase_account_id: &str,
     ) -> Result<DepositInfo, Error> {
+        check_amount(amount)?;
         Ok(self
             .post_and_deserialize(
                 "/deposits/coinbase-account",
@@ -653,16 +1088,37 @@ impl PrivateClient {
             .await?)
     }
 
-    /// Get a list of your coinbase accounts
+    /// Get a list of your coinbase accounts
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#coinbase-accounts)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let accounts = client.get_coinbase_accounts().await.unwrap();
+    /// ~~~~
+    pub async fn get_coinbase_accounts(&self) -> Result<Vec<CoinbaseAccount>, Error> {
+        Ok(self.get("/coinbase-accounts").await?)
+    }
+
+    /// Get the coinbase account of a currency, or `None` if you have no coinbase account for the currency
+    /// <br>
+    /// The `id` of the account is the `coinbase_account_id` used by `deposit_funds_from_coinbase` and `withdraw_to_coinbase`
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#coinbase-accounts)
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// let accounts = client.get_coinbase_accounts().await.unwrap();
+    /// let account = client.get_coinbase_account_by_currency("BTC").await.unwrap();
     /// ~~~~
-    pub async fn get_coinbase_accounts(&self) -> Result<Json, Error> {
-        Ok(self.get("/coinbase-accounts").await?)
+    pub async fn get_coinbase_account_by_currency(
+        &self,
+        currency: &str,
+    ) -> Result<Option<CoinbaseAccount>, Error> {
+        Ok(self
+            .get_coinbase_accounts()
+            .await?
+            .into_iter()
+            .find(|account| account.currency.eq_ignore_ascii_case(currency)))
     }
 
     /// Generate an address for crypto deposits
@@ -698,7 +1154,7 @@ impl PrivateClient {
     /// <br>
     /// *after*: If after is set, then it returns withdrawals created before the after timestamp, sorted by newest
     /// <br>
-    /// *limit*: truncate list to this many withdrawals, capped at 100. Default is 100
+    /// *limit*: truncate list to this many withdrawals, at most 100, a greater limit returns an error of kind `ErrorKind::InvalidParameter`. Default is 100.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-withdrawals)
     /// <br>
@@ -743,7 +1199,7 @@ impl PrivateClient {
     /// *after*: If after is set, then it returns internal withdrawals created before the after timestamp, sorted by newest
     /// <br>
     /// <br>
-    /// *limit*: truncate list to this many internal withdrawals, capped at 100. Default is 100
+    /// *limit*: truncate list to this many internal withdrawals, at most 100, a greater limit returns an error of kind `ErrorKind::InvalidParameter`. Default is 100.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-withdrawals)
     /// <br>
@@ -795,10 +1251,11 @@ impl PrivateClient {
     /// [API docs](https://docs.pro.coinbase.com/#payment-method55)
     pub async fn withdraw_funds(
         &self,
-        amount: f64,
+        amount: &str,
         currency: &str,
         payment_method_id: &str,
     ) -> Result<WithdrawInfo, Error> {
+        check_amount(amount)?;
         Ok(self
             .post_and_deserialize(
                 "/withdrawals/payment-method",
@@ -818,16 +1275,17 @@ impl PrivateClient {
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
     /// let res = client
-    ///     .withdraw_to_coinbase(1.0, "ADA", "91bdfea7-f2sd-5waa-bb0d-5b93c9f09ffc")
+    ///     .withdraw_to_coinbase("1.0", "ADA", "91bdfea7-f2sd-5waa-bb0d-5b93c9f09ffc")
     ///     .await
     ///     .unwrap();    
     /// ~~~~
     pub async fn withdraw_to_coinbase(
         &self,
-        amount: f64,
+        amount: &str,
         currency: &str,
         coinbase_account_id: &str,
     ) -> Result<WithdrawInfo, Error> {
+        check_amount(amount)?;
         Ok(self
             .post_and_deserialize(
                 "/withdrawals/coinbase-account",
@@ -840,6 +1298,30 @@ impl PrivateClient {
             .await?)
     }
 
+    /// Check an amount is within the crypto withdrawal limits of a currency
+    /// <br>
+    /// Fetches the currency and returns an error of kind `ErrorKind::InvalidParameter` if `amount` is below its `min_withdrawal_amount` or above its `max_withdrawal_amount`, which coinbase would reject
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-a-currency)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// client.check_crypto_withdrawal("6.0", "ADA").await.unwrap();
+    /// ~~~~
+    pub async fn check_crypto_withdrawal(&self, amount: &str, currency: &str) -> Result<(), Error> {
+        check_amount(amount)?;
+        let currency: Currency = self.get(&format!("/currencies/{}", currency)).await?;
+        let value = parse_amount(amount)?;
+        let details = &currency.details;
+        if value < details.min_withdrawal_amount || value > details.max_withdrawal_amount {
+            return Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "{} {} is outside the withdrawal limits of {} to {}",
+                amount, currency.id, details.min_withdrawal_amount, details.max_withdrawal_amount
+            ))));
+        }
+        Ok(())
+    }
+
     /// Withdraw funds to a crypto address.
     /// <br>
     /// **parameters**
@@ -856,21 +1338,26 @@ impl PrivateClient {
     /// <br>
     /// add_network_fee_to_total: A boolean flag to add the network fee on top of the amount. If this is blank, it will default to deducting the network fee from the amount.
     /// <br>
+    /// When the fee is deducted the recipient receives less than `amount`, when it is added `amount` is received and more is withdrawn from the account. The fee is returned in `WithdrawInfo::fee`
+    /// <br>
+    /// Coinbase rejects amounts outside the withdrawal limits of the currency, call `check_crypto_withdrawal` first to get the error without sending the withdrawal
+    /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#crypto)
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// let res = client.withdraw_to_crypto_address(6.0, "ADA", "addr1qyk0yr3ht9d6hcqwp8q8j38nxs04npyjauzz9wp5jcfr95h64lvegfk57zmzltj3nmpjff6490ayyvjh0g6sne6hm3hspnnscy", None, None, None).await.unwrap();
+    /// let res = client.withdraw_to_crypto_address("6.0", "ADA", "addr1qyk0yr3ht9d6hcqwp8q8j38nxs04npyjauzz9wp5jcfr95h64lvegfk57zmzltj3nmpjff6490ayyvjh0g6sne6hm3hspnnscy", None, None, None).await.unwrap();
     /// ~~~~
     pub async fn withdraw_to_crypto_address(
         &self,
-        amount: f64,
+        amount: &str,
         currency: &str,
         crypto_address: &str,
         destination_tag: Option<&str>,
         no_destination_tag: Option<bool>,
         add_network_fee_to_total: Option<bool>,
-    ) -> Result<Json, Error> {
+    ) -> Result<WithdrawInfo, Error> {
+        check_amount(amount)?;
         Ok(self
             .post_and_deserialize(
                 "/withdrawals/crypto",
@@ -898,6 +1385,64 @@ impl PrivateClient {
         Ok(self.get("/fees").await?)
     }
 
+    /// Get a stream of your fees which yields when the maker or taker fee rate changes, polling `get_fees` every `interval`
+    /// <br>
+    /// The current fees are yielded as soon as the stream is polled, after that only fees whose rates differ from the last fees yielded. Polling stops when the stream is dropped, and an error fetching the fees is yielded without ending the stream. Requests wait on the client's rate limit if one is set
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-current-fees)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let mut fees = Box::pin(client.watch_fees(Duration::from_secs(3600)));
+    /// while let Some(fees) = fees.next().await {
+    ///     let fees = fees.unwrap();
+    ///     println!("maker {} taker {}", fees.maker_fee_rate, fees.taker_fee_rate);
+    /// }
+    /// ~~~~
+    pub fn watch_fees(&self, interval: Duration) -> impl Stream<Item = Result<Fees, Error>> {
+        // the maker and taker rates of the last fees yielded
+        let last_rates: Option<(String, String)> = None;
+        stream::unfold(
+            (self.clone(), last_rates, true),
+            move |(client, mut last_rates, mut first)| async move {
+                loop {
+                    if !first {
+                        tokio::time::sleep(interval).await;
+                    }
+                    first = false;
+                    let fees = match client.get_fees().await {
+                        Ok(fees) => fees,
+                        Err(e) => return Some((Err(e), (client, last_rates, first))),
+                    };
+                    let rates = (fees.maker_fee_rate.clone(), fees.taker_fee_rate.clone());
+                    if last_rates.as_ref() != Some(&rates) {
+                        last_rates = Some(rates);
+                        return Some((Ok(fees), (client, last_rates, first)));
+                    }
+                }
+            },
+        )
+    }
+
+    /// Get your current fee tier based on your 30-day trailing volume
+    /// <br>
+    /// The tier is looked up in [`FEE_TIERS`](constant.FEE_TIERS.html) using the `usd_volume` returned by [`get_fees`](#method.get_fees)
+    /// <br>
+    /// [Fee schedule](https://pro.coinbase.com/fees)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let tier = client.current_fee_tier().await.unwrap();
+    /// ~~~~
+    pub async fn current_fee_tier(&self) -> Result<FeeTier, Error> {
+        let fees = self.get_fees().await?;
+        let usd_volume = match fees.usd_volume {
+            Some(volume) => parse_amount(&volume)?,
+            None => 0.0,
+        };
+        Ok(FeeTier::for_usd_volume(usd_volume))
+    }
+
     /// Get the network fee estimate when sending to the given address
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#fee-estimate)
@@ -934,7 +1479,7 @@ impl PrivateClient {
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
     /// let conversion = client
-    ///     .convert_stablecoin("USD", "USDC", 10.00)
+    ///     .convert_stablecoin("USD", "USDC", "10.00")
     ///     .await
     ///     .unwrap();
     /// ~~~~
@@ -942,18 +1487,57 @@ impl PrivateClient {
         &self,
         from_currency_id: &str,
         to_currency_id: &str,
-        amount: f64,
+        amount: &str,
     ) -> Result<StablecoinConversion, Error> {
-        Ok(self
-            .post_and_deserialize(
-                "/conversions",
-                Some(serde_json::json!({
-                    "from": from_currency_id,
-                    "to": to_currency_id,
-                    "amount": amount
-                })),
-            )
-            .await?)
+        self.convert_stablecoin_with_options(from_currency_id, to_currency_id, amount, None, None)
+            .await
+    }
+
+    /// Convert between stablecoins within a specific profile
+    /// <br>
+    /// **optional parameters**
+    /// <br>
+    /// *profile_id*: the profile to convert within. By default, the conversion uses the profile of the API key
+    /// <br>
+    /// *nonce*: a client-generated value that makes the conversion idempotent, a conversion retried with the same nonce is only performed once
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#stablecoin-conversions)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let conversion = client
+    ///     .convert_stablecoin_with_options(
+    ///         "USD",
+    ///         "USDC",
+    ///         "10.00",
+    ///         Some("e1d7731f-b7e2-4285-b711-eeec76fc2aff"),
+    ///         Some("1d4d2ad2-a5ce-4b28-ad2c-1b0a4b21a2d5"),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn convert_stablecoin_with_options(
+        &self,
+        from_currency_id: &str,
+        to_currency_id: &str,
+        amount: &str,
+        profile_id: Option<&str>,
+        nonce: Option<&str>,
+    ) -> Result<StablecoinConversion, Error> {
+        check_amount(amount)?;
+        let mut body = serde_json::json!({
+            "from": from_currency_id,
+            "to": to_currency_id,
+            "amount": amount
+        });
+        // omit optional parameters if not supplied
+        if let Some(profile_id) = profile_id {
+            body["profile_id"] = serde_json::json!(profile_id);
+        }
+        if let Some(nonce) = nonce {
+            body["nonce"] = serde_json::json!(nonce);
+        }
+        self.post_and_deserialize("/conversions", Some(body)).await
     }
 
     /// Reports provide batches of historic information about your profile in various human and machine readable forms    
@@ -995,16 +1579,116 @@ impl PrivateClient {
         Ok(self.get(&format!("/reports/{}", report_id)).await?)
     }
 
+    /// Get previously created reports, optionally only those of a type or which haven't expired
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#get-report-status)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let reports = client
+    ///     .get_reports(Some(ReportType::Fills), Some(true))
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_reports(
+        &self,
+        report_type: Option<ReportType>,
+        ignore_expired: Option<bool>,
+    ) -> Result<Vec<ReportInfo>, Error> {
+        let mut params = Vec::new();
+        if let Some(n) = report_type {
+            params.push(format!("type={}", n.as_str()));
+        }
+        if let Some(n) = ignore_expired {
+            params.push(format!("ignore_expired={}", n));
+        }
+        let path = if params.is_empty() {
+            String::from("/reports")
+        } else {
+            format!("/reports?{}", params.join("&"))
+        };
+        self.get(&path).await
+    }
+
+    /// Download the file of a report
+    /// <br>
+    /// Returns an error of kind `ErrorKind::ReportNotReady` if the report's file has not been generated yet
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let report = client
+    ///     .get_report("d4a3e847-b618-454d-bcb3-e77b0ad61600")
+    ///     .await
+    ///     .unwrap();
+    /// let file = client.download_report(&report).await.unwrap();
+    /// ~~~~
+    pub async fn download_report(&self, report: &ReportInfo) -> Result<Bytes, Error> {
+        Ok(self.get_report_file(report).await?.bytes().await?)
+    }
+
+    /// Download the file of a report as a stream of chunks, for reports too large to hold in memory
+    /// <br>
+    /// Returns an error of kind `ErrorKind::ReportNotReady` if the report's file has not been generated yet
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let report = client
+    ///     .get_report("d4a3e847-b618-454d-bcb3-e77b0ad61600")
+    ///     .await
+    ///     .unwrap();
+    /// let stream = client.download_report_stream(&report).await.unwrap();
+    /// futures::pin_mut!(stream);
+    /// while let Some(chunk) = stream.next().await {
+    ///     let chunk = chunk.unwrap();
+    /// }
+    /// ~~~~
+    pub async fn download_report_stream(
+        &self,
+        report: &ReportInfo,
+    ) -> Result<impl Stream<Item = Result<Bytes, Error>>, Error> {
+        Ok(self
+            .get_report_file(report)
+            .await?
+            .bytes_stream()
+            .map_err(Error::from))
+    }
+
+    // requests the file of a report, file urls are presigned so the request isn't signed
+    async fn get_report_file(&self, report: &ReportInfo) -> Result<reqwest::Response, Error> {
+        let file_url = match report.file_url {
+            Some(ref n) => n,
+            None => {
+                return Err(Error::new(ErrorKind::ReportNotReady(
+                    report.status.to_owned(),
+                )))
+            }
+        };
+        Ok(self
+            .reqwest_client
+            .get(file_url)
+            .send()
+            .await?
+            .error_for_status()?)
+    }
+
     /// Get your profiles
     /// <br>
+    /// **optional parameters**
+    /// <br>
+    /// *active*: only return active profiles if `true`, or only inactive profiles if `false`. By default, all profiles are returned
+    /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#list-profiles)
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// let profiles = client.get_profiles().await.unwrap();
+    /// let profiles = client.get_profiles(Some(true)).await.unwrap();
     /// ~~~~
-    pub async fn get_profiles(&self) -> Result<Vec<Profile>, Error> {
-        Ok(self.get("/profiles").await?)
+    pub async fn get_profiles(&self, active: Option<bool>) -> Result<Vec<Profile>, Error> {
+        let path = match active {
+            Some(n) => format!("/profiles?active={}", n),
+            None => String::from("/profiles"),
+        };
+        self.get(&path).await
     }
 
     /// Get a single profile by profile id
@@ -1028,12 +1712,12 @@ impl PrivateClient {
     /// <br>
     /// ~~~~
     /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
-    /// let ok = client
+    /// let transfer = client
     ///     .create_profile_transfer(
     ///         "e1d7731f-b7e2-4285-b711-eeec76fc2aff",
     ///         "3510ac37-1a99-4c9c-9865-15f1bc5a832e",
     ///         "USD",
-    ///         100.00,
+    ///         "100.00",
     ///     )
     ///     .await
     ///     .unwrap();
@@ -1043,41 +1727,88 @@ impl PrivateClient {
         from: &str,
         to: &str,
         currency: &str,
-        amount: f64,
-    ) -> Result<String, Error> {
-        let response = self
-            .post(
-                "/profiles/transfer",
-                Some(serde_json::json!(
-                    {
-                        "from": from,
-                        "to": to,
-                        "currency": currency,
-                        "amount": amount
-                    }
-                )),
-            )
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            let error_message = response.json::<ErrorMessage>().await?;
-            return Err(Error::new(ErrorKind::Status(StatusError::new(
-                status.as_u16(),
-                error_message.message,
-            ))));
-        }
-        Ok(response.text().await?)
+        amount: &str,
+    ) -> Result<ProfileTransfer, Error> {
+        check_amount(amount)?;
+        let transfer = ProfileTransfer {
+            from: from.to_string(),
+            to: to.to_string(),
+            currency: currency.to_string(),
+            amount: amount.to_string(),
+        };
+        // coinbase responds with `OK` rather than json
+        self.post("/profiles/transfer", Some(&transfer)).await?;
+        Ok(transfer)
     }
 
     /// Get cryptographically signed prices ready to be posted on-chain using Open Oracle smart contracts.
     /// <br>
     /// [API docs](https://docs.pro.coinbase.com/#oracle)
-    pub async fn oracle(&self) -> Result<Json, Error> {
-        Ok(self.get("/oracle").await?)
+    pub async fn oracle(&self) -> Result<OracleResponse, Error> {
+        self.get("/oracle").await
+    }
+
+    /// Get the signed prices of the oracle with each ABI encoded message decoded and paired with its signature
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#oracle)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let prices = client.get_signed_prices().await.unwrap();
+    /// ~~~~
+    pub async fn get_signed_prices(&self) -> Result<Vec<SignedPrice>, Error> {
+        self.oracle().await?.signed_prices()
+    }
+}
+
+// maps the errors returned cancelling an order which is done or doesn't exist to `CancelOutcome::AlreadyDone`
+fn cancel_outcome(result: Result<String, Error>) -> Result<CancelOutcome, Error> {
+    match result {
+        Ok(id) => Ok(CancelOutcome::Cancelled { id }),
+        Err(Error {
+            kind: ErrorKind::Status(StatusError { code: 404, .. }),
+        }) => Ok(CancelOutcome::AlreadyDone),
+        Err(Error {
+            kind:
+                ErrorKind::Status(StatusError {
+                    code: 400,
+                    ref message,
+                    ..
+                }),
+        }) if message.eq_ignore_ascii_case("order already done") => Ok(CancelOutcome::AlreadyDone),
+        Err(e) => Err(e),
     }
 }
 
+/// The outcome of `PrivateClient::cancel_order_checked` and `PrivateClient::cancel_order_by_oid_checked`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CancelOutcome {
+    /// The order was open and has been cancelled, holds the ID of the cancelled order
+    Cancelled { id: String },
+    /// The order was already filled or cancelled, or doesn't exist
+    AlreadyDone,
+}
+
+/// The result of `PrivateClient::cancel_orders_checked`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CancelAllResult {
+    /// The ids of the cancelled orders
+    pub cancelled: Vec<String>,
+    /// The ids of orders which were open before cancelling but weren't cancelled
+    pub not_cancelled: Vec<String>,
+}
+
+/// The result of `PrivateClient::portfolio_value`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PortfolioValue {
+    /// The total value of the priced balances, in the quote currency
+    pub value: f64,
+    /// The currencies with a balance but no product against the quote currency
+    pub unpriced: Vec<String>,
+}
+
 /// Limit list of orders to these statuses. Passing `OpenActivePending` returns orders of all statuses.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OrderStatus {
     Open,
     Active,
@@ -1088,8 +1819,92 @@ pub enum OrderStatus {
     OpenActivePending,
 }
 
+/// Parses the status filters `open`, `active` or `pending`
+/// <br>
+/// `OrderStatus` only covers the statuses orders can be listed by, statuses such as `done`, `received` or `rejected`, which coinbase returns in the `status` of an `OrderInfo`, aren't parsed
+/// <br>
+/// ~~~~
+/// let status: OrderStatus = "open".parse().unwrap();
+/// ~~~~
+impl FromStr for OrderStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Self::Open),
+            "active" => Ok(Self::Active),
+            "pending" => Ok(Self::Pending),
+            _ => Err(unrecognized("order status", s)),
+        }
+    }
+}
+
+impl TryFrom<&str> for OrderStatus {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl OrderStatus {
+    // the query string which limits orders to the statuses
+    fn params(&self) -> &'static str {
+        match self {
+            OrderStatus::Open => "status=open",
+            OrderStatus::Active => "status=active",
+            OrderStatus::Pending => "status=pending",
+            OrderStatus::OpenActive => "status=open&status=active",
+            OrderStatus::OpenPending => "status=open&status=pending",
+            OrderStatus::ActivePending => "status=active&status=pending",
+            OrderStatus::OpenActivePending => "status=open&status=active&status=pending",
+        }
+    }
+}
+
+/// The field to sort a list of orders by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderSortedBy {
+    CreatedAt,
+    Price,
+    Size,
+    OrderId,
+    Side,
+    Type,
+}
+
+impl OrderSortedBy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            OrderSortedBy::CreatedAt => "created_at",
+            OrderSortedBy::Price => "price",
+            OrderSortedBy::Size => "size",
+            OrderSortedBy::OrderId => "order_id",
+            OrderSortedBy::Side => "side",
+            OrderSortedBy::Type => "type",
+        }
+    }
+}
+
+/// The direction to sort a list in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sorting {
+    Asc,
+    Desc,
+}
+
+impl Sorting {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Sorting::Asc => "asc",
+            Sorting::Desc => "desc",
+        }
+    }
+}
+
 /// A structure that represents a Stablecoin Conversion
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct StablecoinConversion {
     pub id: String,
     pub amount: String,
@@ -1100,7 +1915,8 @@ pub struct StablecoinConversion {
 }
 
 /// A structure that represents an Account
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Account {
     pub id: String,
     pub currency: String,
@@ -1111,20 +1927,67 @@ pub struct Account {
     pub trading_enabled: bool,
 }
 
+impl Account {
+    /// Returns `true` if `balance` equals `available` plus `hold`
+    /// <br>
+    /// Coinbase can rarely report balances which don't add up, e.g. while a transfer is settling. Amounts are compared exactly rather than with floating point, a negative or unparsable amount is inconsistent
+    pub fn is_consistent(&self) -> bool {
+        is_sum(&self.balance, &self.available, &self.hold)
+    }
+}
+
+/// A structure that represents a Coinbase Account, an account of your coinbase.com wallet
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct CoinbaseAccount {
+    pub id: String,
+    pub name: String,
+    pub balance: String,
+    pub currency: String,
+    /// wallet or fiat
+    pub r#type: String,
+    pub primary: bool,
+    pub active: bool,
+}
+
 /// A structure that represents an Account History
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AccountHistory {
     pub id: String,
     #[serde(deserialize_with = "deserialize_to_date")]
     pub created_at: DateTime<Utc>,
     pub amount: String,
     pub balance: String,
-    pub r#type: String,
+    pub r#type: AccountHistoryType,
     pub details: AccountHistoryDetails,
 }
 
+/// The type of an `AccountHistory` entry, which determines the fields of its `details`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum AccountHistoryType {
+    /// Funds moved to or from the account, `details` has the `transfer_id` and `transfer_type`
+    Transfer,
+    /// Funds moved as the result of a trade, `details` has the `order_id`, `trade_id` and `product_id`
+    Match,
+    /// A fee as the result of a trade, `details` has the `order_id`, `trade_id` and `product_id`
+    Fee,
+    /// A fee rebate as the result of a trade
+    Rebate,
+    /// Funds converted between currencies, e.g. USD and USDC
+    Conversion,
+    /// A type this version doesn't know, coinbase adds ledger entry types over time
+    #[serde(other)]
+    Other,
+}
+
 /// A structure that represents an Account Hold
-#[derive(Deserialize, Debug)]
+/// <br>
+/// Holds are placed on an account for open orders and pending withdrawals, they account for the difference between `balance` and `available`
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Hold {
     pub id: String,
     pub account_id: String,
@@ -1133,49 +1996,116 @@ pub struct Hold {
     #[serde(deserialize_with = "deserialize_to_date")]
     pub updated_at: DateTime<Utc>,
     pub amount: String,
-    pub r#type: String,
+    pub r#type: HoldType,
+    /// The id of the order or transfer which created the hold
     pub r#ref: String,
 }
 
+/// The origin of a `Hold`
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum HoldType {
+    Order,
+    Transfer,
+}
+
+/// The order or transfer which placed a `Hold`, see `PrivateClient::resolve_hold`
+#[derive(Debug)]
+pub enum HoldResolution {
+    // boxed as an `OrderInfo` is much larger than a `Transfer`
+    Order(Box<OrderInfo>),
+    Transfer(Transfer),
+}
+
 /// A structure that represents Account History Details
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct AccountHistoryDetails {
     pub order_id: Option<String>,
     pub trade_id: Option<String>,
     pub product_id: Option<String>,
+    /// The transfer of a `transfer` entry
+    pub transfer_id: Option<String>,
+    /// deposit or withdraw, for a `transfer` entry
+    pub transfer_type: Option<String>,
 }
 
 /// A structure that represents Deposit Info
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct DepositInfo {
     pub id: String,
     pub amount: String,
     pub currency: String,
-    pub payout_at: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub payout_at: Option<DateTime<Utc>>,
 }
 
 /// A structure that represents Withdraw Info
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct WithdrawInfo {
     pub id: String,
     pub amount: String,
     pub currency: String,
+    /// The network fee deducted from a withdrawal to a crypto address, `None` for other withdrawals
+    pub fee: Option<String>,
+    /// The amount of a withdrawal to a crypto address before the fee is deducted, `None` for other withdrawals
+    pub subtotal: Option<String>,
+    /// When a withdrawal to a payment method will be paid out, `None` for other withdrawals
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub payout_at: Option<DateTime<Utc>>,
+}
+
+/// A structure that represents a deposit or withdrawal of an account
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Transfer {
+    pub id: String,
+    /// deposit, withdraw, internal_deposit or internal_withdraw
+    pub r#type: String,
+    #[serde(deserialize_with = "deserialize_to_date")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub canceled_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub processed_at: Option<DateTime<Utc>>,
+    pub account_id: String,
+    pub user_id: String,
+    pub amount: String,
+    pub details: Json,
 }
 
 /// A structure that represents Order Info
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct OrderInfo {
     pub id: String,
-    pub price: String,
-    pub size: String,
+    /// `None` for market orders
+    pub price: Option<String>,
+    /// `None` for market orders placed with funds
+    pub size: Option<String>,
+    /// Funds of a market order placed with funds, less fees
+    pub funds: Option<String>,
+    /// Funds of a market order placed with funds as specified when the order was placed
+    pub specified_funds: Option<String>,
     pub product_id: String,
     pub side: String,
     pub stp: Option<String>,
     pub r#type: String,
-    pub time_in_force: String,
+    /// `None` for market orders
+    pub time_in_force: Option<String>,
     pub post_only: bool,
     #[serde(deserialize_with = "deserialize_to_date")]
     pub created_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "deserialize_option_to_date")]
+    pub done_at: Option<DateTime<Utc>>,
+    pub done_reason: Option<String>,
+    /// Why coinbase rejected the order, e.g. `post only` for a post-only order which would have taken liquidity, `None` unless `status` is `rejected`
+    pub reject_reason: Option<String>,
     pub fill_fees: String,
     pub filled_size: String,
     pub executed_value: String,
@@ -1184,7 +2114,8 @@ pub struct OrderInfo {
 }
 
 /// A structure that represents Report Info
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ReportInfo {
     pub id: String,
     pub r#type: String,
@@ -1200,7 +2131,8 @@ pub struct ReportInfo {
 }
 
 /// A structure that represents Report Info Params
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ReportParams {
     #[serde(deserialize_with = "deserialize_to_date")]
     pub start_date: DateTime<Utc>,
@@ -1209,7 +2141,8 @@ pub struct ReportParams {
 }
 
 /// A structure that represents a Fill
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Fill {
     pub trade_id: u64,
     pub product_id: String,
@@ -1218,22 +2151,271 @@ pub struct Fill {
     pub order_id: String,
     #[serde(deserialize_with = "deserialize_to_date")]
     pub created_at: DateTime<Utc>,
-    pub liquidity: String,
+    pub liquidity: Liquidity,
     pub fee: String,
     pub settled: bool,
-    pub side: String,
+    pub side: OrderSide,
+}
+
+/// Whether a `Fill` provided or took liquidity
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum Liquidity {
+    #[serde(rename = "M")]
+    Maker,
+    #[serde(rename = "T")]
+    Taker,
+}
+
+/// A structure that represents aggregated statistics of fills
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FillStats {
+    pub maker_count: usize,
+    pub taker_count: usize,
+    pub total_fees: f64,
+    /// Total size bought, in the base currency
+    pub buy_volume: f64,
+    /// Total size sold, in the base currency
+    pub sell_volume: f64,
+}
+
+impl FillStats {
+    /// Aggregates fills into `FillStats`
+    pub fn from_fills(fills: &[Fill]) -> Result<FillStats, Error> {
+        let mut stats = FillStats::default();
+        for fill in fills {
+            match fill.liquidity {
+                Liquidity::Maker => stats.maker_count += 1,
+                Liquidity::Taker => stats.taker_count += 1,
+            }
+            stats.total_fees += parse_amount(&fill.fee)?;
+            match fill.side {
+                OrderSide::Buy => stats.buy_volume += parse_amount(&fill.size)?,
+                OrderSide::Sell => stats.sell_volume += parse_amount(&fill.size)?,
+            }
+        }
+        Ok(stats)
+    }
 }
 
 /// A structure that represents your current maker & taker fee rates, as well as your 30-day trailing volume
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Fees {
     pub maker_fee_rate: String,
     pub taker_fee_rate: String,
+    /// `None` for new accounts without a trailing volume
     pub usd_volume: Option<String>,
 }
 
+/// A structure that represents a fee tier of the Coinbase Pro fee schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeTier {
+    pub name: &'static str,
+    pub min_usd_volume: f64,
+    /// `None` for the highest tier
+    pub max_usd_volume: Option<f64>,
+    pub maker_fee_rate: f64,
+    pub taker_fee_rate: f64,
+}
+
+impl FeeTier {
+    /// Get the fee tier for a 30-day trailing USD volume
+    pub fn for_usd_volume(usd_volume: f64) -> FeeTier {
+        FEE_TIERS
+            .iter()
+            .rev()
+            .find(|tier| usd_volume >= tier.min_usd_volume)
+            .copied()
+            .unwrap_or(FEE_TIERS[0])
+    }
+}
+
+/// Coinbase Pro fee tiers ordered by 30-day trailing USD volume
+/// <br>
+/// [Fee schedule](https://pro.coinbase.com/fees)
+pub const FEE_TIERS: [FeeTier; 11] = [
+    FeeTier {
+        name: "$0 - $10K",
+        min_usd_volume: 0.0,
+        max_usd_volume: Some(10_000.0),
+        maker_fee_rate: 0.005,
+        taker_fee_rate: 0.005,
+    },
+    FeeTier {
+        name: "$10K - $50K",
+        min_usd_volume: 10_000.0,
+        max_usd_volume: Some(50_000.0),
+        maker_fee_rate: 0.0035,
+        taker_fee_rate: 0.0035,
+    },
+    FeeTier {
+        name: "$50K - $100K",
+        min_usd_volume: 50_000.0,
+        max_usd_volume: Some(100_000.0),
+        maker_fee_rate: 0.0015,
+        taker_fee_rate: 0.0025,
+    },
+    FeeTier {
+        name: "$100K - $1M",
+        min_usd_volume: 100_000.0,
+        max_usd_volume: Some(1_000_000.0),
+        maker_fee_rate: 0.001,
+        taker_fee_rate: 0.002,
+    },
+    FeeTier {
+        name: "$1M - $10M",
+        min_usd_volume: 1_000_000.0,
+        max_usd_volume: Some(10_000_000.0),
+        maker_fee_rate: 0.0008,
+        taker_fee_rate: 0.0018,
+    },
+    FeeTier {
+        name: "$10M - $50M",
+        min_usd_volume: 10_000_000.0,
+        max_usd_volume: Some(50_000_000.0),
+        maker_fee_rate: 0.0005,
+        taker_fee_rate: 0.0015,
+    },
+    FeeTier {
+        name: "$50M - $100M",
+        min_usd_volume: 50_000_000.0,
+        max_usd_volume: Some(100_000_000.0),
+        maker_fee_rate: 0.0,
+        taker_fee_rate: 0.001,
+    },
+    FeeTier {
+        name: "$100M - $300M",
+        min_usd_volume: 100_000_000.0,
+        max_usd_volume: Some(300_000_000.0),
+        maker_fee_rate: 0.0,
+        taker_fee_rate: 0.0007,
+    },
+    FeeTier {
+        name: "$300M - $500M",
+        min_usd_volume: 300_000_000.0,
+        max_usd_volume: Some(500_000_000.0),
+        maker_fee_rate: 0.0,
+        taker_fee_rate: 0.0006,
+    },
+    FeeTier {
+        name: "$500M - $1B",
+        min_usd_volume: 500_000_000.0,
+        max_usd_volume: Some(1_000_000_000.0),
+        maker_fee_rate: 0.0,
+        taker_fee_rate: 0.0005,
+    },
+    FeeTier {
+        name: "$1B+",
+        min_usd_volume: 1_000_000_000.0,
+        max_usd_volume: None,
+        maker_fee_rate: 0.0,
+        taker_fee_rate: 0.0004,
+    },
+];
+
+/// A structure that represents the signed prices of the Open Oracle
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct OracleResponse {
+    pub timestamp: String,
+    /// ABI encoded `(string kind, uint64 timestamp, string key, uint64 value)` messages
+    pub messages: Vec<String>,
+    /// The signature of the message at the same index
+    pub signatures: Vec<String>,
+    pub prices: HashMap<String, String>,
+}
+
+impl OracleResponse {
+    /// Decodes each message and pairs it with its signature
+    pub fn signed_prices(&self) -> Result<Vec<SignedPrice>, Error> {
+        self.messages
+            .iter()
+            .zip(self.signatures.iter())
+            .map(|(message, signature)| SignedPrice::decode(message, signature))
+            .collect()
+    }
+}
+
+/// A structure that represents a decoded Open Oracle message and its signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedPrice {
+    /// The kind of the message, e.g. `prices`
+    pub kind: String,
+    pub timestamp: u64,
+    /// The asset symbol, e.g. `BTC`
+    pub key: String,
+    /// The price in USD with six decimal places, e.g. `8751625000` is $8751.625
+    pub value: u64,
+    /// The ABI encoded message
+    pub message: String,
+    pub signature: String,
+}
+
+impl SignedPrice {
+    /// Decodes an ABI encoded message and pairs it with its signature
+    pub fn decode(message: &str, signature: &str) -> Result<SignedPrice, Error> {
+        let invalid = || {
+            Error::new(ErrorKind::InvalidParameter(format!(
+                "invalid oracle message: {}",
+                message
+            )))
+        };
+        let bytes = decode_hex(message).ok_or_else(invalid)?;
+        // reads the 32 byte word at offset as an integer, rejecting values wider than 64 bits
+        let word = |offset: usize| -> Option<u64> {
+            let word = bytes.get(offset..offset.checked_add(32)?)?;
+            if word[..24].iter().any(|n| *n != 0) {
+                return None;
+            }
+            let mut n = [0; 8];
+            n.copy_from_slice(&word[24..]);
+            Some(u64::from_be_bytes(n))
+        };
+        // reads the length prefixed string at the offset stored in the word at offset
+        let string = |offset: usize| -> Option<String> {
+            let start = usize::try_from(word(offset)?).ok()?;
+            let len = usize::try_from(word(start)?).ok()?;
+            let data_start = start.checked_add(32)?;
+            let data = bytes.get(data_start..data_start.checked_add(len)?)?;
+            String::from_utf8(data.to_vec()).ok()
+        };
+        Ok(SignedPrice {
+            kind: string(0).ok_or_else(invalid)?,
+            timestamp: word(32).ok_or_else(invalid)?,
+            key: string(64).ok_or_else(invalid)?,
+            value: word(96).ok_or_else(invalid)?,
+            message: message.to_string(),
+            signature: signature.to_string(),
+        })
+    }
+}
+
+// decodes a hex string with an optional 0x prefix
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|n| u8::from_str_radix(hex.get(n..n + 2)?, 16).ok())
+        .collect()
+}
+
+/// A structure that represents a completed transfer between profiles
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileTransfer {
+    /// The id of the profile the funds were transferred from
+    pub from: String,
+    /// The id of the profile the funds were transferred to
+    pub to: String,
+    pub currency: String,
+    pub amount: String,
+}
+
 /// A structure represents a single profile
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Profile {
     pub id: String,
     pub user_id: String,
@@ -1,7 +1,16 @@
-/// A structure that represents a Report
+/// A structure that represents a Report to be created with `PrivateClient::create_report`
+/// <br>
+/// The fields of a `Report` are private, use `ReportBuilder` (or `Report::fills_builder` and `Report::account_builder`) to create one.
+/// <br>
+/// ~~~~
+/// let report = ReportBuilder::fills("2014-11-01T00:00:00.000Z", "2021-06-11T02:48:15.853Z", "BTC-USD")
+///     .format(Format::CSV)
+///     .build();
+/// ~~~~
 #[derive(serde::Serialize, Debug)]
+#[must_use = "a report does nothing until it is created with `PrivateClient::create_report`"]
 pub struct Report {
-    r#type: String,
+    r#type: ReportType,
     start_date: String,
     end_date: String,
     product_id: Option<String>,
@@ -18,7 +27,7 @@ impl Report {
         product_id: &str,
     ) -> impl SharedReportOptions + FillsReportOptions {
         ReportBuilder {
-            r#type: "fills".to_string(),
+            r#type: ReportType::Fills,
             start_date: start_date.to_string(),
             end_date: end_date.to_string(),
             product_id: Some(product_id.to_string()),
@@ -35,7 +44,7 @@ impl Report {
         account_id: &str,
     ) -> impl SharedReportOptions + AccountReportOptions {
         ReportBuilder {
-            r#type: "account".to_string(),
+            r#type: ReportType::Account,
             start_date: start_date.to_string(),
             end_date: end_date.to_string(),
             product_id: None,
@@ -44,12 +53,25 @@ impl Report {
             email: None,
         }
     }
+
+    /// Creates a `ReportBuilder` for type balance
+    pub fn balance_builder(start_date: &str, end_date: &str) -> impl SharedReportOptions {
+        ReportBuilder::balance(start_date, end_date)
+    }
+
+    /// Creates a `ReportBuilder` for type tax-invoice
+    pub fn tax_invoice_builder(start_date: &str, end_date: &str) -> impl SharedReportOptions {
+        ReportBuilder::tax_invoice(start_date, end_date)
+    }
 }
 
 /// A `ReportBuilder` can be used to create a `Report` with custom configuration.
+/// <br>
+/// Each report type has its own constructor taking the parameters the type requires, fills reports require a `product_id` and account reports require an `account_id`.
 #[derive(Debug)]
+#[must_use = "a builder does nothing until it is built"]
 pub struct ReportBuilder {
-    r#type: String,
+    r#type: ReportType,
     start_date: String,
     end_date: String,
     product_id: Option<String>,
@@ -66,7 +88,7 @@ impl ReportBuilder {
         product_id: &str,
     ) -> impl SharedReportOptions + FillsReportOptions {
         Self {
-            r#type: "fills".to_string(),
+            r#type: ReportType::Fills,
             start_date: start_date.to_string(),
             end_date: end_date.to_string(),
             product_id: Some(product_id.to_string()),
@@ -83,7 +105,7 @@ impl ReportBuilder {
         account_id: &str,
     ) -> impl SharedReportOptions + AccountReportOptions {
         Self {
-            r#type: "account".to_string(),
+            r#type: ReportType::Account,
             start_date: start_date.to_string(),
             end_date: end_date.to_string(),
             product_id: None,
@@ -92,9 +114,36 @@ impl ReportBuilder {
             email: None,
         }
     }
+
+    /// Creates a `ReportBuilder` for type balance
+    pub fn balance(start_date: &str, end_date: &str) -> impl SharedReportOptions {
+        Self {
+            r#type: ReportType::Balance,
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            product_id: None,
+            account_id: None,
+            format: Format::PDF,
+            email: None,
+        }
+    }
+
+    /// Creates a `ReportBuilder` for type tax-invoice
+    pub fn tax_invoice(start_date: &str, end_date: &str) -> impl SharedReportOptions {
+        Self {
+            r#type: ReportType::TaxInvoice,
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            product_id: None,
+            account_id: None,
+            format: Format::PDF,
+            email: None,
+        }
+    }
 }
 
 /// Fills only builder options
+#[must_use = "a builder does nothing until it is built"]
 pub trait FillsReportOptions {
     fn account_id(self, account_id: &str) -> Self;
 }
@@ -108,6 +157,7 @@ impl FillsReportOptions for ReportBuilder {
 }
 
 /// Account only builder options
+#[must_use = "a builder does nothing until it is built"]
 pub trait AccountReportOptions {
     fn product_id(self, product_id: &str) -> Self;
 }
@@ -121,6 +171,7 @@ impl AccountReportOptions for ReportBuilder {
 }
 
 /// Fills and account builder options
+#[must_use = "a builder does nothing until it is built"]
 pub trait SharedReportOptions {
     fn format(self, format: Format) -> Self;
     fn email(self, email: &str) -> Self;
@@ -155,6 +206,15 @@ impl SharedReportOptions for ReportBuilder {
 }
 
 /// Type of report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportType {
+    Fills,
+    Account,
+    Balance,
+    TaxInvoice,
+}
+
+/// File format of report
 #[derive(Debug)]
 pub enum Format {
     PDF,
@@ -172,3 +232,23 @@ impl serde::Serialize for Format {
         }
     }
 }
+
+impl ReportType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fills => "fills",
+            Self::Account => "account",
+            Self::Balance => "balance",
+            Self::TaxInvoice => "tax-invoice",
+        }
+    }
+}
+
+impl serde::Serialize for ReportType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::error::{Error, ErrorKind};
+use std::fmt;
+use std::str::FromStr;
+
+/// A product id in the `BASE-QUOTE` format, e.g. `BTC-USD`
+/// <br>
+/// Methods taking a product id accept anything that converts into a `ProductId`, so a `&str` can be passed directly. Parse a `ProductId` with `str::parse` to validate its format.
+/// <br>
+/// ~~~~
+/// let product_id: ProductId = "BTC-USD".parse().unwrap();
+/// assert_eq!(product_id.base(), "BTC");
+/// assert_eq!(product_id.quote(), "USD");
+/// ~~~~
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProductId(String);
+
+impl ProductId {
+    /// Creates a `ProductId` from a base and quote currency
+    pub fn new(base: &str, quote: &str) -> Self {
+        Self(format!("{}-{}", base, quote))
+    }
+
+    /// The base currency, e.g. `BTC` of `BTC-USD`
+    pub fn base(&self) -> &str {
+        match self.0.split_once('-') {
+            Some((base, _)) => base,
+            None => &self.0,
+        }
+    }
+
+    /// The quote currency, e.g. `USD` of `BTC-USD`
+    pub fn quote(&self) -> &str {
+        match self.0.split_once('-') {
+            Some((_, quote)) => quote,
+            None => "",
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for ProductId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_currency = |n: &str| !n.is_empty() && n.chars().all(|c| c.is_ascii_alphanumeric());
+        match s.split_once('-') {
+            Some((base, quote)) if is_currency(base) && is_currency(quote) => {
+                Ok(Self(s.to_string()))
+            }
+            _ => Err(Error::new(ErrorKind::InvalidParameter(format!(
+                "product id {:?} is not in the BASE-QUOTE format",
+                s
+            )))),
+        }
+    }
+}
+
+impl fmt::Display for ProductId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for ProductId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ProductId {
+    fn from(s: &str) -> Self {
+        Self(s.to_string())
+    }
+}
+
+impl From<String> for ProductId {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&ProductId> for ProductId {
+    fn from(product_id: &ProductId) -> Self {
+        product_id.clone()
+    }
+}
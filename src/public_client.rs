@@ -1,15 +1,44 @@
 use super::{
-    deserialize_response, deserialize_to_date, COINBASE_API_URL, COINBASE_SANDBOX_API_URL,
+    check_status, deserialize_response, deserialize_to_date, COINBASE_API_URL,
+    COINBASE_SANDBOX_API_URL,
 };
-use crate::{configure_pagination, error::Error};
-use chrono::{DateTime, Utc};
+use crate::cache::Cache;
+use crate::{
+    config::ClientConfig,
+    configure_pagination,
+    error::{Error, ErrorKind},
+    rate_limit::RateLimiter,
+    ProductId,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use reqwest;
 use serde;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+const DEFAULT_USER_AGENT: &str = "coinbase_client";
+
+// the most candles coinbase returns for a single historic rates request
+const MAX_CANDLES: u32 = 300;
+
+// the most historic rates requests `get_product_candles_range` sends at once
+const CANDLE_REQUESTS: usize = 3;
+
+// the most ticker requests `get_tickers` sends at once
+const TICKER_REQUESTS: usize = 4;
+
+// the most stats requests `get_stats` sends at once
+const STATS_REQUESTS: usize = 4;
 
 /// `PublicClient provides public market data
+#[derive(Clone)]
 pub struct PublicClient {
     reqwest_client: reqwest::Client,
-    url: &'static str,
+    user_agent: reqwest::header::HeaderValue,
+    url: String,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<Cache>,
 }
 
 impl PublicClient {
@@ -23,7 +52,7 @@ impl PublicClient {
     where
         T: serde::de::DeserializeOwned,
     {
-        let params = configure_pagination(before, after, limit);
+        let params = configure_pagination(before, after, limit)?;
         self.get(&format!("{}{}", path, params)).await
     }
 
@@ -31,13 +60,67 @@ impl PublicClient {
     where
         T: serde::de::DeserializeOwned,
     {
-        let response = self
+        deserialize_response(self.send_get(path).await?).await
+    }
+
+    // gets path from the cache if the client has one, otherwise like `get`
+    async fn get_cached<T>(&self, path: &str) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let cache = match self.cache {
+            Some(ref n) => n,
+            None => return self.get(path).await,
+        };
+        let body = match cache.get(path) {
+            Some(n) => n,
+            None => {
+                let body = check_status(self.send_get(path).await?)
+                    .await?
+                    .bytes()
+                    .await?;
+                cache.insert(path, body.clone());
+                body
+            }
+        };
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn send_get(&self, path: &str) -> Result<reqwest::Response, Error> {
+        if let Some(ref n) = self.rate_limiter {
+            n.acquire().await;
+        }
+        Ok(self
             .reqwest_client
             .get(format!("{}{}", self.url, path))
-            .header(reqwest::header::USER_AGENT, "coinbase_client")
+            .header(reqwest::header::USER_AGENT, self.user_agent.clone())
             .send()
-            .await?;
-        deserialize_response(response).await
+            .await?)
+    }
+
+    /// Returns `true` if the client sends requests to the coinbase pro sandbox API
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new_sandbox();
+    /// assert!(client.is_sandbox());
+    /// ~~~~
+    pub fn is_sandbox(&self) -> bool {
+        self.url == COINBASE_SANDBOX_API_URL
+    }
+
+    /// Clears the responses cached because of `cache_ttl`, so the next request of each gets fresh data
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::builder()
+    ///     .cache_ttl(Duration::from_secs(300))
+    ///     .build()
+    ///     .unwrap();
+    /// client.clear_cache();
+    /// ~~~~
+    pub fn clear_cache(&self) {
+        if let Some(ref n) = self.cache {
+            n.clear();
+        }
     }
 
     /// Creates a `PublicClient`
@@ -47,8 +130,11 @@ impl PublicClient {
     /// ~~~~
     pub fn new() -> Self {
         Self {
-            reqwest_client: reqwest::Client::new(),
-            url: COINBASE_API_URL,
+            reqwest_client: ClientConfig::default_reqwest_client(),
+            user_agent: reqwest::header::HeaderValue::from_static(DEFAULT_USER_AGENT),
+            url: COINBASE_API_URL.to_string(),
+            rate_limiter: None,
+            cache: None,
         }
     }
 
@@ -59,8 +145,26 @@ impl PublicClient {
     /// ~~~~
     pub fn new_sandbox() -> Self {
         Self {
-            reqwest_client: reqwest::Client::new(),
-            url: COINBASE_SANDBOX_API_URL,
+            reqwest_client: ClientConfig::default_reqwest_client(),
+            user_agent: reqwest::header::HeaderValue::from_static(DEFAULT_USER_AGENT),
+            url: COINBASE_SANDBOX_API_URL.to_string(),
+            rate_limiter: None,
+            cache: None,
+        }
+    }
+
+    /// Creates a `PublicClientBuilder` to configure a `PublicClient`
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::builder()
+    ///     .pool_max_idle_per_host(4)
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn builder() -> PublicClientBuilder {
+        PublicClientBuilder {
+            config: ClientConfig::default(),
+            url: COINBASE_API_URL.to_string(),
         }
     }
 
@@ -73,7 +177,7 @@ impl PublicClient {
     /// let products = client.get_products().await.unwrap();
     /// ~~~~
     pub async fn get_products(&self) -> Result<Vec<Product>, Error> {
-        let products: Vec<Product> = self.get("/products").await?;
+        let products: Vec<Product> = self.get_cached("/products").await?;
         Ok(products)
     }
 
@@ -85,19 +189,23 @@ impl PublicClient {
     /// let client = PublicClient::new();
     /// let product = client.get_product("BTC-USD").await.unwrap();
     /// ~~~~
-    pub async fn get_product(&self, id: &str) -> Result<Product, Error> {
-        let product: Product = self.get(&format!("/products/{}", id)).await?;
+    pub async fn get_product(&self, id: impl Into<ProductId>) -> Result<Product, Error> {
+        let product: Product = self.get(&format!("/products/{}", id.into())).await?;
         Ok(product)
     }
 
     // Get a list of open orders for a product
     async fn get_order_book(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
         level: OrderLevel,
     ) -> Result<OrderBook<BookEntry>, Error> {
         let book: OrderBook<BookEntry> = self
-            .get(&format!("/products/{}/book?level={}", id, level as u8))
+            .get(&format!(
+                "/products/{}/book?level={}",
+                id.into(),
+                level as u8
+            ))
             .await?;
         Ok(book)
     }
@@ -112,10 +220,62 @@ impl PublicClient {
     /// let client = PublicClient::new();
     /// let order_book = client.get_product_order_book("BTC-USD").await.unwrap();
     /// ~~~~
-    pub async fn get_product_order_book(&self, id: &str) -> Result<OrderBook<BookEntry>, Error> {
+    pub async fn get_product_order_book(
+        &self,
+        id: impl Into<ProductId>,
+    ) -> Result<OrderBook<BookEntry>, Error> {
         Ok(self.get_order_book(id, OrderLevel::One).await?)
     }
 
+    /// Get the best bid and ask of a product
+    /// <br>
+    /// Requests the level 1 order book, the cheapest order book request, and returns its top entries as `(bid, ask)`. Returns an error of kind `ErrorKind::EmptyOrderBook` if the book has no bids or no asks
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-product-order-book)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let (bid, ask) = client.best_bid_ask("BTC-USD").await.unwrap();
+    /// ~~~~
+    pub async fn best_bid_ask(
+        &self,
+        id: impl Into<ProductId>,
+    ) -> Result<(BookEntry, BookEntry), Error> {
+        let book = self.get_product_order_book(id).await?;
+        match (book.bids.into_iter().next(), book.asks.into_iter().next()) {
+            (Some(bid), Some(ask)) => Ok((bid, ask)),
+            _ => Err(Error::new(ErrorKind::EmptyOrderBook)),
+        }
+    }
+
+    /// Get the ticker, best bid and ask, and 24 hr stats of a product
+    /// <br>
+    /// Requests the ticker, the level 1 order book and the 24 hr stats concurrently, returning an error if any of them fails
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-product-ticker)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let snapshot = client.get_market_snapshot("BTC-USD").await.unwrap();
+    /// println!("last {}, 24h high {}", snapshot.ticker.price, snapshot.stats.high);
+    /// ~~~~
+    pub async fn get_market_snapshot(
+        &self,
+        id: impl Into<ProductId>,
+    ) -> Result<MarketSnapshot, Error> {
+        let id = id.into();
+        let (ticker, book, stats) = futures::try_join!(
+            self.get_product_ticker(&id, None, None, None),
+            self.get_product_order_book(&id),
+            self.get_product_24hr_stats(&id)
+        )?;
+        Ok(MarketSnapshot {
+            ticker,
+            book,
+            stats,
+        })
+    }
+
     /// Get a list of open orders for a product
     /// <br>
     /// Gets top 50 bids and asks
@@ -131,7 +291,7 @@ impl PublicClient {
     /// ~~~~
     pub async fn get_product_order_book_top50(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
     ) -> Result<OrderBook<BookEntry>, Error> {
         Ok(self.get_order_book(id, OrderLevel::Two).await?)
     }
@@ -148,10 +308,11 @@ impl PublicClient {
     /// ~~~~
     pub async fn get_product_order_book_all(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
     ) -> Result<OrderBook<FullBookEntry>, Error> {
-        let book: OrderBook<FullBookEntry> =
-            self.get(&format!("/products/{}/book?level=3", id)).await?;
+        let book: OrderBook<FullBookEntry> = self
+            .get(&format!("/products/{}/book?level=3", id.into()))
+            .await?;
         Ok(book)
     }
 
@@ -170,17 +331,78 @@ impl PublicClient {
     /// ~~~~
     pub async fn get_product_ticker(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
         before: Option<&str>,
         after: Option<&str>,
         limit: Option<u16>,
     ) -> Result<Ticker, Error> {
         let ticker = self
-            .get_paginated(&format!("/products/{}/ticker?", id), before, after, limit)
+            .get_paginated(
+                &format!("/products/{}/ticker?", id.into()),
+                before,
+                after,
+                limit,
+            )
             .await?;
         Ok(ticker)
     }
 
+    /// Get the tickers of several products, a few requests at a time
+    /// <br>
+    /// Returns a map from product id to the product's ticker, or the error fetching it, so one failing product doesn't fail the others
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-product-ticker)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let tickers = client.get_tickers(&["BTC-USD", "ETH-USD"]).await;
+    /// ~~~~
+    pub async fn get_tickers(
+        &self,
+        product_ids: &[&str],
+    ) -> HashMap<String, Result<Ticker, Error>> {
+        let tickers: Vec<_> = stream::iter(product_ids)
+            .map(|id| self.get_product_ticker(*id, None, None, None))
+            .buffered(TICKER_REQUESTS)
+            .collect()
+            .await;
+        product_ids
+            .iter()
+            .map(|id| id.to_string())
+            .zip(tickers)
+            .collect()
+    }
+
+    /// Get a stream of the ticker of a product, polling `get_product_ticker` every `interval`
+    /// <br>
+    /// The first ticker is requested as soon as the stream is polled. Polling stops when the stream is dropped, and an error fetching a ticker is yielded without ending the stream
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-product-ticker)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let mut tickers = Box::pin(client.ticker_stream("BTC-USD", Duration::from_secs(1)));
+    /// while let Some(ticker) = tickers.next().await {
+    ///     println!("{}", ticker.unwrap().price);
+    /// }
+    /// ~~~~
+    pub fn ticker_stream(
+        &self,
+        id: impl Into<ProductId>,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<Ticker, Error>> {
+        stream::unfold(
+            (self.clone(), id.into(), true),
+            move |(client, id, first)| async move {
+                if !first {
+                    tokio::time::sleep(interval).await;
+                }
+                let ticker = client.get_product_ticker(&id, None, None, None).await;
+                Some((ticker, (client, id, false)))
+            },
+        )
+    }
+
     /// Get the latest trades for a product.
     ///<br>
     ///<br>
@@ -205,13 +427,18 @@ impl PublicClient {
     /// ~~~~
     pub async fn get_product_trades(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
         before: Option<&str>,
         after: Option<&str>,
         limit: Option<u16>,
     ) -> Result<Vec<Trade>, Error> {
         let trades: Vec<Trade> = self
-            .get_paginated(&format!("/products/{}/trades?", id), before, after, limit)
+            .get_paginated(
+                &format!("/products/{}/trades?", id.into()),
+                before,
+                after,
+                limit,
+            )
             .await?;
         Ok(trades)
     }
@@ -238,13 +465,13 @@ impl PublicClient {
     /// ~~~~
     pub async fn get_product_historic_rates(
         &self,
-        id: &str,
+        id: impl Into<ProductId>,
         start: Option<&str>,
         end: Option<&str>,
         granularity: Option<Granularity>,
     ) -> Result<Vec<HistoricRate>, Error> {
         let mut appended = false;
-        let mut path = format!("/products/{}/candles", id);
+        let mut path = format!("/products/{}/candles", id.into());
         if let Some(n) = start {
             appended = true;
             path.push_str(&format!("?start={}", n));
@@ -268,6 +495,104 @@ impl PublicClient {
         Ok(rates)
     }
 
+    /// Get the candles of a product from `start` to `end`, oldest first
+    /// <br>
+    /// Coinbase returns at most 300 candles per request, so the range is split into windows of at most 300 candles which are requested a few at a time. Candles are deduplicated by `time`
+    /// <br>
+    /// Returns an error of kind `ErrorKind::InvalidParameter` if `end` is before `start`
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-historic-rates)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let end = Utc::now();
+    /// let candles = client
+    ///     .get_product_candles_range("BTC-USD", end - chrono::Duration::days(365), end, Granularity::OneDay)
+    ///     .await
+    ///     .unwrap();
+    /// ~~~~
+    pub async fn get_product_candles_range(
+        &self,
+        id: impl Into<ProductId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        granularity: Granularity,
+    ) -> Result<Vec<HistoricRate>, Error> {
+        if end < start {
+            return Err(Error::new(ErrorKind::InvalidParameter(
+                "end of the candle range is before its start".to_string(),
+            )));
+        }
+        let id = id.into();
+        let window = chrono::Duration::seconds(granularity as i64 * MAX_CANDLES as i64);
+        let mut windows = Vec::new();
+        let mut window_start = start;
+        loop {
+            let window_end = (window_start + window).min(end);
+            windows.push((window_start, window_end));
+            if window_end >= end {
+                break;
+            }
+            window_start = window_end;
+        }
+        let chunks: Vec<Vec<HistoricRate>> = stream::iter(windows)
+            .map(|(window_start, window_end)| {
+                let id = id.clone();
+                async move {
+                    self.get_product_historic_rates(
+                        id,
+                        Some(&window_start.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                        Some(&window_end.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                        Some(granularity),
+                    )
+                    .await
+                }
+            })
+            .buffer_unordered(CANDLE_REQUESTS)
+            .try_collect()
+            .await?;
+        // candles keyed by start time, windows overlap at their boundaries
+        let (start, end) = (start.timestamp() as u64, end.timestamp() as u64);
+        let candles: BTreeMap<u64, HistoricRate> = chunks
+            .into_iter()
+            .flatten()
+            .filter(|rate| rate.time >= start && rate.time <= end)
+            .map(|rate| (rate.time, rate))
+            .collect();
+        Ok(candles.into_values().collect())
+    }
+
+    /// Get the total volume of a product over the last `days` days, including the current day, in the base currency
+    /// <br>
+    /// Sums the volume of daily candles from `get_product_candles_range`, which requests ranges longer than 300 days in chunks
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-historic-rates)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let volume = client.get_daily_volume("BTC-USD", 30).await.unwrap();
+    /// ~~~~
+    pub async fn get_daily_volume(
+        &self,
+        id: impl Into<ProductId>,
+        days: u32,
+    ) -> Result<f64, Error> {
+        if days == 0 {
+            return Ok(0.0);
+        }
+        let end = Utc::now();
+        let start = end - chrono::Duration::days(days as i64);
+        let candles = self
+            .get_product_candles_range(id, start, end, Granularity::OneDay)
+            .await?;
+        Ok(candles
+            .iter()
+            .rev()
+            .take(days as usize)
+            .map(|rate| rate.volume)
+            .sum())
+    }
+
     /// Get 24 hr stats for the product
     /// <br>
     /// [api docs](https://docs.pro.coinbase.com/#get-24hr-stats)
@@ -276,11 +601,41 @@ impl PublicClient {
     /// let client = PublicClient::new();
     /// let twenty_four_hour_stats = client.get_product_24hr_stats("BTC-USD").await.unwrap();
     /// ~~~~
-    pub async fn get_product_24hr_stats(&self, id: &str) -> Result<TwentyFourHourStats, Error> {
-        let stats: TwentyFourHourStats = self.get(&format!("/products/{}/stats", id)).await?;
+    pub async fn get_product_24hr_stats(
+        &self,
+        id: impl Into<ProductId>,
+    ) -> Result<TwentyFourHourStats, Error> {
+        let stats: TwentyFourHourStats =
+            self.get(&format!("/products/{}/stats", id.into())).await?;
         Ok(stats)
     }
 
+    /// Get the 24 hr stats of several products, a few requests at a time
+    /// <br>
+    /// Returns a map from product id to the product's stats, or the error fetching them, so one failing product doesn't fail the others
+    /// <br>
+    /// [api docs](https://docs.pro.coinbase.com/#get-24hr-stats)
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::new();
+    /// let stats = client.get_stats(&["BTC-USD", "ETH-USD"]).await;
+    /// ~~~~
+    pub async fn get_stats(
+        &self,
+        product_ids: &[&str],
+    ) -> HashMap<String, Result<TwentyFourHourStats, Error>> {
+        let stats: Vec<_> = stream::iter(product_ids)
+            .map(|id| self.get_product_24hr_stats(*id))
+            .buffered(STATS_REQUESTS)
+            .collect()
+            .await;
+        product_ids
+            .iter()
+            .map(|id| id.to_string())
+            .zip(stats)
+            .collect()
+    }
+
     /// Get known currencies
     /// <br>
     /// [api docs](https://docs.pro.coinbase.com/#get-currencies)
@@ -290,7 +645,7 @@ impl PublicClient {
     /// let currencies = client.get_currencies().await.unwrap();
     /// ~~~~
     pub async fn get_currencies(&self) -> Result<Vec<Currency>, Error> {
-        let currencies: Vec<Currency> = self.get("/currencies").await?;
+        let currencies: Vec<Currency> = self.get_cached("/currencies").await?;
         Ok(currencies)
     }
 
@@ -320,8 +675,117 @@ impl PublicClient {
     }
 }
 
+/// A `PublicClientBuilder` can be used to create a `PublicClient` with custom configuration.
+#[must_use = "a builder does nothing until it is built"]
+pub struct PublicClientBuilder {
+    config: ClientConfig,
+    url: String,
+}
+
+impl PublicClientBuilder {
+    /// Use the coinbase pro sandbox API
+    pub fn sandbox(mut self) -> Self {
+        self.url = COINBASE_SANDBOX_API_URL.to_string();
+        self
+    }
+
+    /// Use a custom base url, e.g. a gateway which proxies requests to the coinbase pro API
+    /// <br>
+    /// Requests are sent to the base url followed by the path of the endpoint
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets how long idle connections are kept alive in the connection pool (default is 90 seconds)
+    /// <br>
+    /// Pass `None` to keep idle connections alive indefinitely
+    pub fn pool_idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.config.pool_idle_timeout = timeout.into();
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host (default is unlimited)
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.config.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Sets the user agent sent with each request (default is `coinbase_client`)
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.config.user_agent = Some(user_agent);
+        self
+    }
+
+    /// Sets whether gzip and brotli compressed responses are accepted (default is `true`)
+    /// <br>
+    /// Compressed responses are decompressed before they are deserialized, disabling compression can be useful to inspect responses while debugging
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.config.compression = enabled;
+        self
+    }
+
+    /// Routes requests through `proxy`, call multiple times to add proxies for different schemes
+    /// <br>
+    /// The `HTTP_PROXY` and `HTTPS_PROXY` environment variables are ignored unless `env_proxy` is called
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::builder()
+    ///     .proxy(reqwest::Proxy::https("http://proxy.example.com:8080").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.config.proxies.push(proxy);
+        self
+    }
+
+    /// Routes requests through the proxies of the `HTTP_PROXY` and `HTTPS_PROXY` environment variables (default is no proxy)
+    /// <br>
+    /// Adding a proxy with `proxy` replaces the proxies of the environment
+    /// <br>
+    /// ~~~~
+    /// let client = PublicClient::builder()
+    ///     .env_proxy()
+    ///     .build()
+    ///     .unwrap();
+    /// ~~~~
+    pub fn env_proxy(mut self) -> Self {
+        self.config.env_proxy = true;
+        self
+    }
+
+    /// Limits requests to `requests_per_second`, allowing bursts of up to `burst` requests (default is unlimited)
+    /// <br>
+    /// Requests wait until they are within the limit rather than being rejected by coinbase with a 429. Clones of the client share the limit, so it holds when the client is cloned across tasks, but separately built clients are each limited on their own
+    pub fn rate_limit(mut self, requests_per_second: u32, burst: u32) -> Self {
+        self.config.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+
+    /// Caches the responses of `get_products` and `get_currencies` for `ttl` (default is no caching)
+    /// <br>
+    /// A cached response is returned rather than sending a request until it is older than `ttl`, so the data may be up to `ttl` old. Clones of the client share the cache, call `clear_cache` to get fresh data before the ttl expires
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Builds `PublicClient`
+    pub fn build(self) -> Result<PublicClient, Error> {
+        Ok(PublicClient {
+            reqwest_client: self.config.build_reqwest_client()?,
+            user_agent: self.config.user_agent(DEFAULT_USER_AGENT)?,
+            url: self.url,
+            rate_limiter: self.config.rate_limiter()?,
+            cache: self.config.cache(),
+        })
+    }
+}
+
 /// A structure that represents a product
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Product {
     pub id: String,
     pub display_name: String,
@@ -341,14 +805,29 @@ pub struct Product {
     pub trading_disabled: bool,
 }
 
-#[derive(serde::Deserialize, Debug)]
+impl Product {
+    /// Returns `true` if the product is online and accepts all order types
+    /// <br>
+    /// A product in cancel only, limit only or post only mode, or with trading disabled, rejects some or all new orders
+    pub fn is_tradable(&self) -> bool {
+        self.status == "online"
+            && !self.trading_disabled
+            && !self.cancel_only
+            && !self.post_only
+            && !self.limit_only
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct BookEntry {
     pub price: String,
     pub size: String,
     pub num_orders: u64,
 }
 
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct FullBookEntry {
     pub price: String,
     pub size: String,
@@ -356,7 +835,8 @@ pub struct FullBookEntry {
 }
 
 /// A structure that represents the trade list of open orders for a product
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct OrderBook<T> {
     pub bids: Vec<T>,
     pub asks: Vec<T>,
@@ -364,7 +844,8 @@ pub struct OrderBook<T> {
 }
 
 /// A structure that represents a trade
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Trade {
     #[serde(deserialize_with = "deserialize_to_date")]
     pub time: DateTime<Utc>,
@@ -375,7 +856,8 @@ pub struct Trade {
 }
 
 /// A structure that represents latest trades for a product
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Ticker {
     pub trade_id: u64,
     pub price: String,
@@ -387,8 +869,20 @@ pub struct Ticker {
     pub time: DateTime<Utc>,
 }
 
+impl Ticker {
+    /// How long ago the last trade of the ticker happened by the local clock, zero if `time` is ahead of the local clock
+    /// <br>
+    /// Use it to avoid acting on a stale ticker, e.g. of a product which hasn't traded for a while
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.time)
+            .to_std()
+            .unwrap_or_else(|_| Duration::from_secs(0))
+    }
+}
+
 /// A structure that represents rates for a product
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct HistoricRate {
     pub time: u64,
     pub low: f64,
@@ -399,7 +893,8 @@ pub struct HistoricRate {
 }
 
 /// A structure that represents 24 hr stats for a product
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct TwentyFourHourStats {
     pub open: String,
     pub high: String,
@@ -409,8 +904,18 @@ pub struct TwentyFourHourStats {
     pub volume_30day: String,
 }
 
+/// The result of `PublicClient::get_market_snapshot`
+#[derive(serde::Serialize, Debug)]
+pub struct MarketSnapshot {
+    pub ticker: Ticker,
+    /// The level 1 order book, with only the best bid and ask
+    pub book: OrderBook<BookEntry>,
+    pub stats: TwentyFourHourStats,
+}
+
 /// A structure that represents a currency
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Currency {
     pub id: String,
     pub name: String,
@@ -422,7 +927,20 @@ pub struct Currency {
     pub details: CurrencyDetails,
 }
 
-#[derive(serde::Deserialize, Debug)]
+impl Currency {
+    /// The number of decimals of `max_precision`, e.g. 8 for a `max_precision` of `0.00000001`
+    /// <br>
+    /// Use it to format amounts of the currency, such as the balance of an `Account`
+    pub fn decimals(&self) -> usize {
+        match self.max_precision.split_once('.') {
+            Some((_, fraction)) => fraction.trim_end_matches('0').len(),
+            None => 0,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct CurrencyDetails {
     pub r#type: String, // use raw identifier to allow reserved keyword
     pub symbol: Option<String>,
@@ -439,7 +957,8 @@ pub struct CurrencyDetails {
 }
 
 /// A structure that represents the API server time.
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Time {
     #[serde(deserialize_with = "deserialize_to_date")]
     pub iso: DateTime<Utc>,
@@ -452,6 +971,7 @@ enum OrderLevel {
 }
 
 /// Desired timeslice in seconds {60, 300, 900, 3600, 21600, 86400}
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Granularity {
     OneMinute = 60,
     FiveMinutes = 300,
@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+// a token bucket limiting the rate of requests, clones share the same bucket so clones of a client used across tasks are limited together
+#[derive(Clone, Debug)]
+pub(crate) struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    // tokens added per second
+    rate: f64,
+    // the most tokens the bucket holds, the number of requests which can be sent at once
+    burst: f64,
+    // negative when requests are waiting for tokens
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: u32, burst: u32) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                rate: requests_per_second as f64,
+                burst: burst as f64,
+                tokens: burst as f64,
+                refilled_at: Instant::now(),
+            })),
+        }
+    }
+
+    // waits until a request may be sent
+    pub(crate) async fn acquire(&self) {
+        // reserve a token, then wait for it outside the lock so requests are sent in the order they acquired
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * bucket.rate).min(bucket.burst);
+            bucket.refilled_at = now;
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / bucket.rate)
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_waits_once_burst_is_used() {
+        let limiter = RateLimiter::new(10, 2);
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_bucket() {
+        let limiter = RateLimiter::new(10, 1);
+        let clone = limiter.clone();
+        let start = Instant::now();
+        limiter.acquire().await;
+        clone.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::error::{Error, ErrorKind};
+use crate::Json;
+
+/// `BookSync` synchronizes an order book snapshot from the REST API with the messages of the `full` channel of the websocket feed
+/// <br>
+/// Subscribe to the feed before requesting the snapshot, then pass each message to `push`. Messages are buffered until `set_snapshot` is called with the `sequence` of the snapshot, after which messages already part of the snapshot (`sequence <= snapshot sequence`) are discarded and the rest are returned in order to be applied to the book. Messages without a `sequence`, such as `subscriptions`, are returned immediately.
+/// <br>
+/// `push` and `set_snapshot` return an error of kind `ErrorKind::SequenceGap` when a message was missed. The book can no longer be kept up to date from the feed, so `BookSync` goes back to buffering until `set_snapshot` is called with a new snapshot.
+/// <br>
+/// Sequence numbers are per product, use a `BookSync` for each product
+/// <br>
+/// [API docs](https://docs.pro.coinbase.com/#real-time-order-book)
+/// <br>
+/// ~~~~
+/// let mut feed = WebSocketFeed::connect(&["BTC-USD"], &["full"]).await.unwrap();
+/// let mut sync = BookSync::new();
+/// // buffer messages received while the snapshot is requested
+/// let snapshot = PublicClient::new().get_product_order_book_all("BTC-USD");
+/// tokio::pin!(snapshot);
+/// let snapshot = loop {
+///     tokio::select! {
+///         snapshot = &mut snapshot => break snapshot.unwrap(),
+///         Some(message) = feed.next() => { sync.push(message.unwrap()).unwrap(); }
+///     }
+/// };
+/// for message in sync.set_snapshot(snapshot.sequence).unwrap() {
+///     // apply message to the book
+/// }
+/// while let Some(message) = feed.next().await {
+///     for message in sync.push(message.unwrap()).unwrap() {
+///         // apply message to the book
+///     }
+/// }
+/// ~~~~
+#[derive(Debug, Default)]
+pub struct BookSync {
+    // messages received before the snapshot
+    buffer: Vec<(u64, Json)>,
+    // sequence of the last message returned, `None` while buffering
+    last_sequence: Option<u64>,
+}
+
+impl BookSync {
+    /// Creates a `BookSync` which buffers messages until the snapshot is set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the sequence of the order book snapshot, returning the buffered messages which are newer than the snapshot ordered by sequence
+    /// <br>
+    /// Returns an error of kind `ErrorKind::SequenceGap` if a message between the snapshot and the newest buffered message is missing, including when the oldest buffered message is newer than `sequence + 1` because the snapshot is stale. `BookSync` then keeps buffering until `set_snapshot` is called with a new snapshot, like after a gap reported by `push`
+    pub fn set_snapshot(&mut self, sequence: u64) -> Result<Vec<Json>, Error> {
+        self.buffer.sort_by_key(|(n, _)| *n);
+        // messages of the feed can be duplicated while buffering
+        self.buffer.dedup_by_key(|(n, _)| *n);
+        self.buffer.retain(|(n, _)| *n > sequence);
+        let mut expected = sequence + 1;
+        for (n, _) in &self.buffer {
+            if *n != expected {
+                // drop the messages before the gap, the next snapshot replaces them
+                let received = *n;
+                self.buffer.retain(|(n, _)| *n >= received);
+                self.last_sequence = None;
+                return Err(Error::new(ErrorKind::SequenceGap { expected, received }));
+            }
+            expected += 1;
+        }
+        self.last_sequence = Some(expected - 1);
+        Ok(self.buffer.drain(..).map(|(_, message)| message).collect())
+    }
+
+    /// Buffers a message of the feed until the snapshot is set, or once it is, returns the message if it is newer than the snapshot
+    pub fn push(&mut self, message: Json) -> Result<Vec<Json>, Error> {
+        let sequence = match message["sequence"].as_u64() {
+            Some(n) => n,
+            None => return Ok(vec![message]),
+        };
+        let last_sequence = match self.last_sequence {
+            Some(n) => n,
+            None => {
+                self.buffer.push((sequence, message));
+                return Ok(Vec::new());
+            }
+        };
+        if sequence <= last_sequence {
+            return Ok(Vec::new());
+        }
+        if sequence > last_sequence + 1 {
+            // keep the message, it may be newer than the next snapshot
+            self.last_sequence = None;
+            self.buffer.push((sequence, message));
+            return Err(Error::new(ErrorKind::SequenceGap {
+                expected: last_sequence + 1,
+                received: sequence,
+            }));
+        }
+        self.last_sequence = Some(sequence);
+        Ok(vec![message])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(sequence: u64) -> Json {
+        serde_json::json!({
+            "type": "open",
+            "product_id": "BTC-USD",
+            "sequence": sequence
+        })
+    }
+
+    fn sequences(messages: &[Json]) -> Vec<u64> {
+        messages
+            .iter()
+            .map(|n| n["sequence"].as_u64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_buffers_until_snapshot() {
+        let mut sync = BookSync::new();
+        for n in &[11, 9, 10, 12] {
+            assert!(sync.push(message(*n)).unwrap().is_empty());
+        }
+        assert_eq!(sequences(&sync.set_snapshot(10).unwrap()), vec![11, 12]);
+        assert_eq!(sequences(&sync.push(message(13)).unwrap()), vec![13]);
+        // already applied
+        assert!(sync.push(message(12)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_messages_without_sequence_pass_through() {
+        let mut sync = BookSync::new();
+        let subscriptions = serde_json::json!({"type": "subscriptions", "channels": []});
+        assert_eq!(
+            sync.push(subscriptions.clone()).unwrap(),
+            vec![subscriptions]
+        );
+    }
+
+    #[test]
+    fn test_gap_requires_new_snapshot() {
+        let mut sync = BookSync::new();
+        sync.set_snapshot(10).unwrap();
+        sync.push(message(11)).unwrap();
+        let error = sync.push(message(13)).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ErrorKind::SequenceGap {
+                expected: 12,
+                received: 13
+            }
+        ));
+        assert!(sync.push(message(14)).unwrap().is_empty());
+        assert_eq!(sequences(&sync.set_snapshot(13).unwrap()), vec![14]);
+    }
+
+    #[test]
+    fn test_snapshot_detects_gaps_in_buffer() {
+        let mut sync = BookSync::new();
+        for n in &[11, 13, 14] {
+            sync.push(message(*n)).unwrap();
+        }
+        let error = sync.set_snapshot(10).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ErrorKind::SequenceGap {
+                expected: 12,
+                received: 13
+            }
+        ));
+        // still buffering, the messages after the gap are kept for the next snapshot
+        assert!(sync.push(message(15)).unwrap().is_empty());
+        assert_eq!(sequences(&sync.set_snapshot(13).unwrap()), vec![14, 15]);
+    }
+
+    #[test]
+    fn test_stale_snapshot() {
+        let mut sync = BookSync::new();
+        sync.push(message(12)).unwrap();
+        let error = sync.set_snapshot(10).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ErrorKind::SequenceGap {
+                expected: 11,
+                received: 12
+            }
+        ));
+        assert_eq!(sequences(&sync.set_snapshot(11).unwrap()), vec![12]);
+    }
+}
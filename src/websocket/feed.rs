@@ -0,0 +1,104 @@
+use crate::error::Error;
+use crate::private_client::PrivateClient;
+use crate::{Json, COINBASE_SANDBOX_WEBSOCKET_URL, COINBASE_WEBSOCKET_URL};
+use futures::{ready, SinkExt, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+/// `WebSocketFeed` streams the messages of the coinbase pro websocket feed
+/// <br>
+/// Each message is yielded as `Json`, including the `subscriptions` message confirming the subscription and any `error` message. The stream ends when the feed closes the connection
+/// <br>
+/// [API docs](https://docs.pro.coinbase.com/#websocket-feed)
+/// <br>
+/// ~~~~
+/// let mut feed = WebSocketFeed::connect(&["BTC-USD"], &["ticker"]).await.unwrap();
+/// while let Some(message) = feed.next().await {
+///     println!("{}", message.unwrap());
+/// }
+/// ~~~~
+pub struct WebSocketFeed {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl WebSocketFeed {
+    /// Connects to the websocket feed and subscribes to `channels` for `product_ids`
+    pub async fn connect(product_ids: &[&str], channels: &[&str]) -> Result<Self, Error> {
+        Self::connect_to(COINBASE_WEBSOCKET_URL, product_ids, channels).await
+    }
+
+    /// Connects to the websocket feed of the coinbase pro sandbox and subscribes to `channels` for `product_ids`
+    pub async fn connect_sandbox(product_ids: &[&str], channels: &[&str]) -> Result<Self, Error> {
+        Self::connect_to(COINBASE_SANDBOX_WEBSOCKET_URL, product_ids, channels).await
+    }
+
+    /// Connects to the websocket feed and subscribes to `channels` for `product_ids`, authenticated with the API key of `client`
+    /// <br>
+    /// Authentication is required for the `user` channel, which only sends the messages of the API key's orders, and adds the `user_id` and `profile_id` of the API key's orders to the messages of the `full` channel. The sandbox feed is used if `client` uses the sandbox API
+    /// <br>
+    /// [API docs](https://docs.pro.coinbase.com/#subscribe)
+    /// <br>
+    /// ~~~~
+    /// let client = PrivateClient::new("tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==", "t9riylyad0r", "4a9f6de8bcdee641a0a207613dfb43ef");
+    /// let mut feed = WebSocketFeed::connect_authenticated(&client, &["BTC-USD"], &["user"]).await.unwrap();
+    /// ~~~~
+    pub async fn connect_authenticated(
+        client: &PrivateClient,
+        product_ids: &[&str],
+        channels: &[&str],
+    ) -> Result<Self, Error> {
+        let url = if client.is_sandbox() {
+            COINBASE_SANDBOX_WEBSOCKET_URL
+        } else {
+            COINBASE_WEBSOCKET_URL
+        };
+        let mut subscribe = subscribe_message(product_ids, channels);
+        client.sign_subscription(&mut subscribe)?;
+        Self::connect_with(url, &subscribe).await
+    }
+
+    async fn connect_to(url: &str, product_ids: &[&str], channels: &[&str]) -> Result<Self, Error> {
+        Self::connect_with(url, &subscribe_message(product_ids, channels)).await
+    }
+
+    // connects to the feed at url and sends the subscribe message
+    pub(super) async fn connect_with(url: &str, subscribe: &Json) -> Result<Self, Error> {
+        let (mut stream, _) = tokio_tungstenite::connect_async(url).await?;
+        stream.send(Message::Text(subscribe.to_string())).await?;
+        Ok(Self { stream })
+    }
+}
+
+// the message subscribing to `channels` for `product_ids`
+pub(super) fn subscribe_message<T: AsRef<str>>(product_ids: &[T], channels: &[T]) -> Json {
+    let strs = |n: &[T]| n.iter().map(|n| n.as_ref().to_string()).collect::<Vec<_>>();
+    serde_json::json!({
+        "type": "subscribe",
+        "product_ids": strs(product_ids),
+        "channels": strs(channels)
+    })
+}
+
+impl Stream for WebSocketFeed {
+    type Item = Result<Json, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.stream.poll_next_unpin(cx)) {
+                Some(Ok(n)) => n,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => return Poll::Ready(None),
+            };
+            match message {
+                Message::Text(text) => {
+                    return Poll::Ready(Some(serde_json::from_str(&text).map_err(Error::from)))
+                }
+                Message::Close(_) => return Poll::Ready(None),
+                // pings are answered by tungstenite, the feed only sends text messages
+                _ => continue,
+            }
+        }
+    }
+}
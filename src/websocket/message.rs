@@ -0,0 +1,689 @@
+use super::{Level2Change, Level2Entry};
+use crate::error::{Error, ErrorKind};
+use crate::private_client::OrderSide;
+use crate::Json;
+use crate::{deserialize_option_to_date, deserialize_to_date};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A message of a `ReconnectingFeed`
+/// <br>
+/// Prices and sizes are kept as the decimal strings coinbase sends, like the response structures of the REST API
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum FeedMessage {
+    /// The feed reconnected after the connection was lost
+    /// <br>
+    /// Messages sent while disconnected were missed, so state kept from the feed, such as an order book, needs to be resynchronized
+    Reconnected,
+    /// The level 2 order book of a product, sent on the `level2` channel after subscribing
+    Snapshot {
+        product_id: String,
+        /// Bids in the order coinbase sends them, from the highest price
+        bids: Vec<Level2Entry>,
+        /// Asks in the order coinbase sends them, from the lowest price
+        asks: Vec<Level2Entry>,
+    },
+    /// Changes of price levels of a product's order book on the `level2` channel, a size of `0` means the level was removed
+    L2Update {
+        product_id: String,
+        time: DateTime<Utc>,
+        changes: Vec<Level2Change>,
+    },
+    /// A trade of a product on the `matches` channel, or the product's last trade, sent after subscribing
+    /// <br>
+    /// `side` is the side of the maker order, a `Sell` side means the taker bought
+    Match {
+        trade_id: u64,
+        sequence: u64,
+        maker_order_id: String,
+        taker_order_id: String,
+        time: DateTime<Utc>,
+        product_id: String,
+        size: String,
+        price: String,
+        side: OrderSide,
+    },
+    /// The price and best bid and ask of a product on the `ticker` channel, sent after each trade
+    /// <br>
+    /// The ticker sent after subscribing has no trade, so its `trade_id`, `time`, `side` and `last_size` are `None`
+    Ticker {
+        product_id: String,
+        sequence: u64,
+        price: String,
+        best_bid: String,
+        best_ask: String,
+        open_24h: String,
+        high_24h: String,
+        low_24h: String,
+        volume_24h: String,
+        volume_30d: String,
+        trade_id: Option<u64>,
+        time: Option<DateTime<Utc>>,
+        /// The side of the taker order
+        side: Option<OrderSide>,
+        last_size: Option<String>,
+    },
+    /// An order was received by coinbase, on the `user` and `full` channels
+    /// <br>
+    /// `client_oid` is only sent on the `user` channel. A limit order has a `size` and `price`, a market order a `size` or `funds`
+    Received {
+        order_id: String,
+        client_oid: Option<String>,
+        product_id: String,
+        sequence: u64,
+        time: DateTime<Utc>,
+        side: OrderSide,
+        /// `limit` or `market`
+        order_type: String,
+        size: Option<String>,
+        price: Option<String>,
+        funds: Option<String>,
+    },
+    /// The remainder of an order is now open on the order book, on the `user` and `full` channels
+    Open {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        time: DateTime<Utc>,
+        side: OrderSide,
+        price: String,
+        remaining_size: String,
+    },
+    /// An order is no longer on the order book, on the `user` and `full` channels
+    /// <br>
+    /// `reason` is `filled` or `canceled`. Market orders have no `price` or `remaining_size`
+    Done {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        time: DateTime<Utc>,
+        side: OrderSide,
+        reason: String,
+        price: Option<String>,
+        remaining_size: Option<String>,
+    },
+    /// An order changed because of self-trade prevention or a modification, on the `user` and `full` channels
+    /// <br>
+    /// A limit order has the `new_size` and `old_size`, a market order the `new_funds` and `old_funds`
+    Change {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        time: DateTime<Utc>,
+        side: OrderSide,
+        price: Option<String>,
+        new_size: Option<String>,
+        old_size: Option<String>,
+        new_funds: Option<String>,
+        old_funds: Option<String>,
+    },
+    /// A stop order was activated, on the `user` and `full` channels
+    Activate {
+        order_id: String,
+        product_id: String,
+        side: OrderSide,
+        /// `loss` or `entry`
+        stop_type: String,
+        stop_price: String,
+        size: Option<String>,
+        funds: Option<String>,
+    },
+    /// A message of a type which isn't modelled by another variant, such as the `subscriptions` message confirming the subscription or a `heartbeat`
+    /// <br>
+    /// New types of messages are yielded as `Unknown` rather than failing the stream
+    Unknown(Json),
+}
+
+// the typed messages of the feed
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum TypedMessage {
+    #[serde(rename = "snapshot")]
+    Snapshot {
+        product_id: String,
+        bids: Vec<(String, String)>,
+        asks: Vec<(String, String)>,
+    },
+    #[serde(rename = "l2update")]
+    L2Update {
+        product_id: String,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        changes: Vec<(OrderSide, String, String)>,
+    },
+    #[serde(rename = "match", alias = "last_match")]
+    Match {
+        trade_id: u64,
+        sequence: u64,
+        maker_order_id: String,
+        taker_order_id: String,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        product_id: String,
+        size: String,
+        price: String,
+        side: OrderSide,
+    },
+    #[serde(rename = "ticker")]
+    Ticker {
+        product_id: String,
+        sequence: u64,
+        price: String,
+        best_bid: String,
+        best_ask: String,
+        open_24h: String,
+        high_24h: String,
+        low_24h: String,
+        volume_24h: String,
+        volume_30d: String,
+        trade_id: Option<u64>,
+        #[serde(default, deserialize_with = "deserialize_option_to_date")]
+        time: Option<DateTime<Utc>>,
+        side: Option<OrderSide>,
+        last_size: Option<String>,
+    },
+    #[serde(rename = "received")]
+    Received {
+        order_id: String,
+        client_oid: Option<String>,
+        product_id: String,
+        sequence: u64,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        side: OrderSide,
+        order_type: String,
+        size: Option<String>,
+        price: Option<String>,
+        funds: Option<String>,
+    },
+    #[serde(rename = "open")]
+    Open {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        side: OrderSide,
+        price: String,
+        remaining_size: String,
+    },
+    #[serde(rename = "done")]
+    Done {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        side: OrderSide,
+        reason: String,
+        price: Option<String>,
+        remaining_size: Option<String>,
+    },
+    #[serde(rename = "change")]
+    Change {
+        order_id: String,
+        product_id: String,
+        sequence: u64,
+        #[serde(deserialize_with = "deserialize_to_date")]
+        time: DateTime<Utc>,
+        side: OrderSide,
+        price: Option<String>,
+        new_size: Option<String>,
+        old_size: Option<String>,
+        new_funds: Option<String>,
+        old_funds: Option<String>,
+    },
+    #[serde(rename = "activate")]
+    Activate {
+        order_id: String,
+        product_id: String,
+        side: OrderSide,
+        stop_type: String,
+        stop_price: String,
+        size: Option<String>,
+        funds: Option<String>,
+    },
+    #[serde(rename = "error")]
+    Error {
+        message: String,
+        // the cause of a failed subscription, e.g. an invalid signature
+        reason: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+impl FeedMessage {
+    // converts a message of the feed, an `error` message is an error of kind `ErrorKind::Feed`
+    pub(super) fn from_json(message: Json) -> Result<Self, Error> {
+        Ok(match TypedMessage::deserialize(&message)? {
+            TypedMessage::Snapshot {
+                product_id,
+                bids,
+                asks,
+            } => Self::Snapshot {
+                product_id,
+                bids: entries(bids),
+                asks: entries(asks),
+            },
+            TypedMessage::L2Update {
+                product_id,
+                time,
+                changes,
+            } => Self::L2Update {
+                product_id,
+                time,
+                changes: changes
+                    .into_iter()
+                    .map(|(side, price, size)| Level2Change { side, price, size })
+                    .collect(),
+            },
+            TypedMessage::Match {
+                trade_id,
+                sequence,
+                maker_order_id,
+                taker_order_id,
+                time,
+                product_id,
+                size,
+                price,
+                side,
+            } => Self::Match {
+                trade_id,
+                sequence,
+                maker_order_id,
+                taker_order_id,
+                time,
+                product_id,
+                size,
+                price,
+                side,
+            },
+            TypedMessage::Ticker {
+                product_id,
+                sequence,
+                price,
+                best_bid,
+                best_ask,
+                open_24h,
+                high_24h,
+                low_24h,
+                volume_24h,
+                volume_30d,
+                trade_id,
+                time,
+                side,
+                last_size,
+            } => Self::Ticker {
+                product_id,
+                sequence,
+                price,
+                best_bid,
+                best_ask,
+                open_24h,
+                high_24h,
+                low_24h,
+                volume_24h,
+                volume_30d,
+                trade_id,
+                time,
+                side,
+                last_size,
+            },
+            TypedMessage::Received {
+                order_id,
+                client_oid,
+                product_id,
+                sequence,
+                time,
+                side,
+                order_type,
+                size,
+                price,
+                funds,
+            } => Self::Received {
+                order_id,
+                client_oid,
+                product_id,
+                sequence,
+                time,
+                side,
+                order_type,
+                size,
+                price,
+                funds,
+            },
+            TypedMessage::Open {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                price,
+                remaining_size,
+            } => Self::Open {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                price,
+                remaining_size,
+            },
+            TypedMessage::Done {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                reason,
+                price,
+                remaining_size,
+            } => Self::Done {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                reason,
+                price,
+                remaining_size,
+            },
+            TypedMessage::Change {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                price,
+                new_size,
+                old_size,
+                new_funds,
+                old_funds,
+            } => Self::Change {
+                order_id,
+                product_id,
+                sequence,
+                time,
+                side,
+                price,
+                new_size,
+                old_size,
+                new_funds,
+                old_funds,
+            },
+            TypedMessage::Activate {
+                order_id,
+                product_id,
+                side,
+                stop_type,
+                stop_price,
+                size,
+                funds,
+            } => Self::Activate {
+                order_id,
+                product_id,
+                side,
+                stop_type,
+                stop_price,
+                size,
+                funds,
+            },
+            TypedMessage::Error { message, reason } => {
+                let message = match reason {
+                    Some(reason) => format!("{}: {}", message, reason),
+                    None => message,
+                };
+                return Err(Error::new(ErrorKind::Feed(message)));
+            }
+            TypedMessage::Other => Self::Unknown(message),
+        })
+    }
+}
+
+// converts the [price, size] pairs of a snapshot
+fn entries(entries: Vec<(String, String)>) -> Vec<Level2Entry> {
+    entries
+        .into_iter()
+        .map(|(price, size)| Level2Entry { price, size })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_level2_messages() {
+        let snapshot = FeedMessage::from_json(serde_json::json!({
+            "type": "snapshot",
+            "product_id": "BTC-USD",
+            "bids": [["10101.10", "0.45054140"]],
+            "asks": [["10102.55", "0.57753524"]]
+        }))
+        .unwrap();
+        assert_eq!(
+            snapshot,
+            FeedMessage::Snapshot {
+                product_id: "BTC-USD".to_string(),
+                bids: vec![Level2Entry {
+                    price: "10101.10".to_string(),
+                    size: "0.45054140".to_string()
+                }],
+                asks: vec![Level2Entry {
+                    price: "10102.55".to_string(),
+                    size: "0.57753524".to_string()
+                }],
+            }
+        );
+        let update = FeedMessage::from_json(serde_json::json!({
+            "type": "l2update",
+            "product_id": "BTC-USD",
+            "time": "2019-08-14T20:42:27.265Z",
+            "changes": [["buy", "10101.80000000", "0.162567"]]
+        }))
+        .unwrap();
+        assert_eq!(
+            update,
+            FeedMessage::L2Update {
+                product_id: "BTC-USD".to_string(),
+                time: Utc.ymd(2019, 8, 14).and_hms_milli(20, 42, 27, 265),
+                changes: vec![Level2Change {
+                    side: OrderSide::Buy,
+                    price: "10101.80000000".to_string(),
+                    size: "0.162567".to_string()
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_match_message() {
+        let message = serde_json::json!({
+            "type": "match",
+            "trade_id": 10,
+            "sequence": 50,
+            "maker_order_id": "ac928c66-ca53-498f-9c13-a110027a60e8",
+            "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
+            "time": "2014-11-07T08:19:27.028459Z",
+            "product_id": "BTC-USD",
+            "size": "5.23512",
+            "price": "400.23",
+            "side": "sell"
+        });
+        let expected = FeedMessage::Match {
+            trade_id: 10,
+            sequence: 50,
+            maker_order_id: "ac928c66-ca53-498f-9c13-a110027a60e8".to_string(),
+            taker_order_id: "132fb6ae-456b-4654-b4e0-d681ac05cea1".to_string(),
+            time: Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28459),
+            product_id: "BTC-USD".to_string(),
+            size: "5.23512".to_string(),
+            price: "400.23".to_string(),
+            side: OrderSide::Sell,
+        };
+        assert_eq!(FeedMessage::from_json(message.clone()).unwrap(), expected);
+        // the last trade sent after subscribing
+        let mut last_match = message;
+        last_match["type"] = "last_match".into();
+        assert_eq!(FeedMessage::from_json(last_match).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_ticker_message() {
+        let mut message = serde_json::json!({
+            "type": "ticker",
+            "trade_id": 20153558,
+            "sequence": 3262786978u64,
+            "time": "2017-09-02T17:05:49.250000Z",
+            "product_id": "BTC-USD",
+            "price": "4388.01000000",
+            "side": "buy",
+            "last_size": "0.03000000",
+            "best_bid": "4388",
+            "best_ask": "4388.01",
+            "open_24h": "4300.00",
+            "high_24h": "4400.00",
+            "low_24h": "4200.00",
+            "volume_24h": "12000.5",
+            "volume_30d": "350000.25"
+        });
+        match FeedMessage::from_json(message.clone()).unwrap() {
+            FeedMessage::Ticker {
+                price,
+                best_bid,
+                trade_id,
+                side,
+                time,
+                ..
+            } => {
+                assert_eq!(price, "4388.01000000");
+                assert_eq!(best_bid, "4388");
+                assert_eq!(trade_id, Some(20153558));
+                assert_eq!(side, Some(OrderSide::Buy));
+                assert_eq!(
+                    time,
+                    Some(Utc.ymd(2017, 9, 2).and_hms_milli(17, 5, 49, 250))
+                );
+            }
+            other => panic!("expected a ticker, got {:?}", other),
+        }
+        // the ticker sent after subscribing has no trade
+        for field in &["trade_id", "time", "side", "last_size"] {
+            message.as_object_mut().unwrap().remove(*field);
+        }
+        assert!(matches!(
+            FeedMessage::from_json(message).unwrap(),
+            FeedMessage::Ticker {
+                trade_id: None,
+                time: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_user_messages() {
+        let received = FeedMessage::from_json(serde_json::json!({
+            "type": "received",
+            "time": "2014-11-07T08:19:27.028459Z",
+            "product_id": "BTC-USD",
+            "sequence": 10,
+            "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+            "client_oid": "8ac4ec34-3a9e-4b2a-a9b4-1a3b4ff2a3c5",
+            "size": "1.34",
+            "price": "502.1",
+            "side": "buy",
+            "order_type": "limit",
+            "user_id": "5844eceecf7e803e259d0365",
+            "profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352"
+        }))
+        .unwrap();
+        assert!(matches!(
+            received,
+            FeedMessage::Received {
+                sequence: 10,
+                side: OrderSide::Buy,
+                client_oid: Some(_),
+                funds: None,
+                ..
+            }
+        ));
+        let done = FeedMessage::from_json(serde_json::json!({
+            "type": "done",
+            "time": "2014-11-07T08:19:27.028459Z",
+            "product_id": "BTC-USD",
+            "sequence": 11,
+            "price": "200.2",
+            "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+            "reason": "filled",
+            "side": "sell",
+            "remaining_size": "0"
+        }))
+        .unwrap();
+        match done {
+            FeedMessage::Done {
+                reason,
+                remaining_size,
+                ..
+            } => {
+                assert_eq!(reason, "filled");
+                assert_eq!(remaining_size.as_deref(), Some("0"));
+            }
+            other => panic!("expected a done message, got {:?}", other),
+        }
+        let activate = FeedMessage::from_json(serde_json::json!({
+            "type": "activate",
+            "product_id": "BTC-USD",
+            "timestamp": "1483736448.299000",
+            "user_id": "12",
+            "profile_id": "30000727-d308-cf50-7b1c-c06deb1934fc",
+            "order_id": "7b52009b-64fd-0a2a-49e6-d8a939753077",
+            "stop_type": "entry",
+            "side": "buy",
+            "stop_price": "80",
+            "size": "2",
+            "funds": "50",
+            "taker_fee_rate": "0.0025",
+            "private": true
+        }))
+        .unwrap();
+        assert!(matches!(
+            activate,
+            FeedMessage::Activate {
+                side: OrderSide::Buy,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_unknown_and_error_messages() {
+        let subscriptions = serde_json::json!({"type": "subscriptions", "channels": []});
+        assert_eq!(
+            FeedMessage::from_json(subscriptions.clone()).unwrap(),
+            FeedMessage::Unknown(subscriptions)
+        );
+        let status = serde_json::json!({"type": "status", "products": []});
+        assert_eq!(
+            FeedMessage::from_json(status.clone()).unwrap(),
+            FeedMessage::Unknown(status)
+        );
+        let error = FeedMessage::from_json(
+            serde_json::json!({"type": "error", "message": "Failed to subscribe"}),
+        )
+        .unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Feed(_)));
+        let error = FeedMessage::from_json(serde_json::json!({
+            "type": "error",
+            "message": "Failed to subscribe",
+            "reason": "invalid signature"
+        }))
+        .unwrap_err();
+        assert!(
+            matches!(error.kind, ErrorKind::Feed(ref n) if n == "Failed to subscribe: invalid signature")
+        );
+    }
+}
@@ -0,0 +1,10 @@
+pub use book_sync::*;
+pub use feed::*;
+pub use message::*;
+pub use order_book::*;
+pub use reconnecting_feed::*;
+mod book_sync;
+mod feed;
+mod message;
+mod order_book;
+mod reconnecting_feed;
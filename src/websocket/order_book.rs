@@ -0,0 +1,310 @@
+use super::{FeedMessage, WebSocketFeed};
+use crate::error::Error;
+use crate::private_client::OrderSide;
+use crate::{parse_amount, ProductId};
+use chrono::{DateTime, Utc};
+use futures::{ready, Stream, StreamExt};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// `OrderBookStream` maintains the level 2 order book of a product from the `level2` channel of the websocket feed
+/// <br>
+/// The book is built from the `snapshot` message and kept up to date by applying each `l2update` message. Updates received before the snapshot, or older than the last applied update, are discarded. The stream yields an `OrderBookEvent` each time the book changes, use `book` to get the current book
+/// <br>
+/// [API docs](https://docs.pro.coinbase.com/#the-level2-channel)
+/// <br>
+/// ~~~~
+/// let mut order_book = OrderBookStream::connect("BTC-USD").await.unwrap();
+/// while let Some(event) = order_book.next().await {
+///     event.unwrap();
+///     println!("{:?}", order_book.book().bids.first());
+/// }
+/// ~~~~
+pub struct OrderBookStream {
+    feed: WebSocketFeed,
+    book: Book,
+}
+
+impl OrderBookStream {
+    /// Connects to the websocket feed and subscribes to the `level2` channel for `product_id`
+    pub async fn connect(product_id: impl Into<ProductId>) -> Result<Self, Error> {
+        let product_id = product_id.into();
+        let feed = WebSocketFeed::connect(&[product_id.as_str()], &["level2"]).await?;
+        Ok(Self::new(feed, product_id))
+    }
+
+    /// Connects to the websocket feed of the coinbase pro sandbox and subscribes to the `level2` channel for `product_id`
+    pub async fn connect_sandbox(product_id: impl Into<ProductId>) -> Result<Self, Error> {
+        let product_id = product_id.into();
+        let feed = WebSocketFeed::connect_sandbox(&[product_id.as_str()], &["level2"]).await?;
+        Ok(Self::new(feed, product_id))
+    }
+
+    fn new(feed: WebSocketFeed, product_id: ProductId) -> Self {
+        Self {
+            feed,
+            book: Book::new(product_id.to_string()),
+        }
+    }
+
+    /// Returns the current order book, which is empty until the snapshot has been received
+    pub fn book(&self) -> Level2Book {
+        self.book.to_level2_book()
+    }
+}
+
+impl Stream for OrderBookStream {
+    type Item = Result<OrderBookEvent, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.feed.poll_next_unpin(cx)) {
+                Some(Ok(n)) => n,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => return Poll::Ready(None),
+            };
+            match self.book.apply(message) {
+                Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+/// A structure that represents a level 2 order book
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level2Book {
+    pub product_id: String,
+    /// Bids ordered from the highest price
+    pub bids: Vec<Level2Entry>,
+    /// Asks ordered from the lowest price
+    pub asks: Vec<Level2Entry>,
+}
+
+/// A structure that represents the aggregated size of the orders at a price
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level2Entry {
+    pub price: String,
+    pub size: String,
+}
+
+/// A structure that represents a change of a price level, a size of `0` means the level was removed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level2Change {
+    pub side: OrderSide,
+    pub price: String,
+    pub size: String,
+}
+
+/// A change of an `OrderBookStream`'s book
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderBookEvent {
+    /// The book was replaced by a snapshot
+    Snapshot,
+    /// Price levels of the book were changed
+    Update {
+        time: DateTime<Utc>,
+        changes: Vec<Level2Change>,
+    },
+}
+
+// a price which can be used as the key of a BTreeMap
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Price(f64);
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+// price levels of an order book, keyed by price so they stay ordered
+struct Book {
+    product_id: String,
+    bids: BTreeMap<Price, Level2Entry>,
+    asks: BTreeMap<Price, Level2Entry>,
+    // time of the last applied update, `None` until the snapshot is received
+    last_update: Option<DateTime<Utc>>,
+    has_snapshot: bool,
+}
+
+impl Book {
+    fn new(product_id: String) -> Self {
+        Self {
+            product_id,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            last_update: None,
+            has_snapshot: false,
+        }
+    }
+
+    // applies a message of the feed, returning the resulting event or `None` if the book didn't change
+    fn apply(&mut self, message: crate::Json) -> Result<Option<OrderBookEvent>, Error> {
+        match FeedMessage::from_json(message)? {
+            FeedMessage::Snapshot { bids, asks, .. } => {
+                self.bids = levels(bids)?;
+                self.asks = levels(asks)?;
+                self.last_update = None;
+                self.has_snapshot = true;
+                Ok(Some(OrderBookEvent::Snapshot))
+            }
+            FeedMessage::L2Update { time, changes, .. } => {
+                // updates from before the snapshot are already part of it
+                if !self.has_snapshot {
+                    return Ok(None);
+                }
+                if let Some(last_update) = self.last_update {
+                    if time < last_update {
+                        return Ok(None);
+                    }
+                }
+                let mut applied = Vec::with_capacity(changes.len());
+                for change in changes {
+                    let levels = match change.side {
+                        OrderSide::Buy => &mut self.bids,
+                        OrderSide::Sell => &mut self.asks,
+                    };
+                    let key = Price(parse_amount(&change.price)?);
+                    if parse_amount(&change.size)? == 0.0 {
+                        levels.remove(&key);
+                    } else {
+                        levels.insert(
+                            key,
+                            Level2Entry {
+                                price: change.price.clone(),
+                                size: change.size.clone(),
+                            },
+                        );
+                    }
+                    applied.push(change);
+                }
+                self.last_update = Some(time);
+                Ok(Some(OrderBookEvent::Update {
+                    time,
+                    changes: applied,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn to_level2_book(&self) -> Level2Book {
+        Level2Book {
+            product_id: self.product_id.clone(),
+            bids: self.bids.values().rev().cloned().collect(),
+            asks: self.asks.values().cloned().collect(),
+        }
+    }
+}
+
+// keys the price levels of a snapshot by price
+fn levels(entries: Vec<Level2Entry>) -> Result<BTreeMap<Price, Level2Entry>, Error> {
+    entries
+        .into_iter()
+        .map(|entry| Ok((Price(parse_amount(&entry.price)?), entry)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    fn snapshot() -> crate::Json {
+        serde_json::json!({
+            "type": "snapshot",
+            "product_id": "BTC-USD",
+            "bids": [["10101.10", "0.45054140"], ["10101.20", "1.0"]],
+            "asks": [["10102.55", "0.57753524"], ["10103.00", "2.0"]]
+        })
+    }
+
+    fn update(time: &str, side: &str, price: &str, size: &str) -> crate::Json {
+        serde_json::json!({
+            "type": "l2update",
+            "product_id": "BTC-USD",
+            "time": time,
+            "changes": [[side, price, size]]
+        })
+    }
+
+    #[test]
+    fn test_snapshot_orders_levels() {
+        let mut book = Book::new("BTC-USD".to_string());
+        assert_eq!(
+            book.apply(snapshot()).unwrap(),
+            Some(OrderBookEvent::Snapshot)
+        );
+        let book = book.to_level2_book();
+        assert_eq!(book.bids[0].price, "10101.20");
+        assert_eq!(book.bids[1].price, "10101.10");
+        assert_eq!(book.asks[0].price, "10102.55");
+        assert_eq!(book.asks[1].price, "10103.00");
+    }
+
+    #[test]
+    fn test_update_changes_and_removes_levels() {
+        let mut book = Book::new("BTC-USD".to_string());
+        book.apply(snapshot()).unwrap();
+        book.apply(update("2019-08-14T20:42:27.265Z", "buy", "10101.20", "0"))
+            .unwrap();
+        book.apply(update(
+            "2019-08-14T20:42:27.266Z",
+            "sell",
+            "10102.50",
+            "0.5",
+        ))
+        .unwrap();
+        let book = book.to_level2_book();
+        assert_eq!(book.bids.len(), 1);
+        assert_eq!(book.bids[0].price, "10101.10");
+        assert_eq!(
+            book.asks[0],
+            Level2Entry {
+                price: "10102.50".to_string(),
+                size: "0.5".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_stale_updates_are_discarded() {
+        let mut book = Book::new("BTC-USD".to_string());
+        // an update before the snapshot is already part of the snapshot
+        assert_eq!(
+            book.apply(update("2019-08-14T20:42:27.265Z", "buy", "10101.20", "0"))
+                .unwrap(),
+            None
+        );
+        book.apply(snapshot()).unwrap();
+        book.apply(update("2019-08-14T20:42:27.265Z", "buy", "10101.20", "3.0"))
+            .unwrap();
+        assert_eq!(
+            book.apply(update("2019-08-14T20:42:27.264Z", "buy", "10101.20", "0"))
+                .unwrap(),
+            None
+        );
+        assert_eq!(book.to_level2_book().bids[0].size, "3.0");
+    }
+
+    #[test]
+    fn test_error_message() {
+        let mut book = Book::new("BTC-USD".to_string());
+        let error = book
+            .apply(serde_json::json!({"type": "error", "message": "Failed to subscribe"}))
+            .unwrap_err();
+        assert!(matches!(error.kind, ErrorKind::Feed(_)));
+    }
+}
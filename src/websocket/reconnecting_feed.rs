@@ -0,0 +1,264 @@
+use super::feed::subscribe_message;
+use super::{FeedMessage, WebSocketFeed};
+use crate::error::{Error, ErrorKind};
+use crate::private_client::PrivateClient;
+use crate::{COINBASE_SANDBOX_WEBSOCKET_URL, COINBASE_WEBSOCKET_URL};
+use futures::future::BoxFuture;
+use futures::{ready, FutureExt, Stream, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::time::Sleep;
+
+/// `ReconnectingFeed` streams the messages of the coinbase pro websocket feed, reconnecting when the connection is lost
+/// <br>
+/// When the feed closes the connection, the connection fails, or no message arrives within `ReconnectingFeedBuilder::heartbeat_timeout`, `ReconnectingFeed` reconnects with exponential backoff, sends the subscribe message again and yields `FeedMessage::Reconnected`. An `error` message of the feed is yielded as an error of kind `ErrorKind::Feed`. The stream ends with the error of the last attempt once `ReconnectingFeedBuilder::max_retries` attempts in a row have failed
+/// <br>
+/// [API docs](https://docs.pro.coinbase.com/#websocket-feed)
+/// <br>
+/// ~~~~
+/// let mut feed = ReconnectingFeed::builder(&["BTC-USD"], &["ticker"])
+///     .backoff(Duration::from_secs(1), Duration::from_secs(30))
+///     .max_retries(10)
+///     .connect()
+///     .await
+///     .unwrap();
+/// while let Some(message) = feed.next().await {
+///     match message.unwrap() {
+///         FeedMessage::Reconnected => println!("reconnected, resynchronize"),
+///         message => println!("{:?}", message),
+///     }
+/// }
+/// ~~~~
+pub struct ReconnectingFeed {
+    subscription: Subscription,
+    state: State,
+    // fires when no message has arrived within the heartbeat timeout, `None` without a timeout
+    watchdog: Option<Pin<Box<Sleep>>>,
+    last_heartbeat: Option<Instant>,
+}
+
+enum State {
+    Connected(Box<WebSocketFeed>),
+    Reconnecting(BoxFuture<'static, Result<WebSocketFeed, Error>>),
+    Closed,
+}
+
+// what to connect to and how to reconnect, moved into each reconnection
+#[derive(Clone)]
+struct Subscription {
+    url: String,
+    product_ids: Vec<String>,
+    channels: Vec<String>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_retries: Option<u32>,
+    heartbeat_timeout: Option<Duration>,
+    // signs the subscribe message of each connection, so its timestamp is current
+    client: Option<PrivateClient>,
+}
+
+impl Subscription {
+    async fn connect(&self) -> Result<WebSocketFeed, Error> {
+        let mut subscribe = subscribe_message(&self.product_ids, &self.channels);
+        if let Some(ref client) = self.client {
+            client.sign_subscription(&mut subscribe)?;
+        }
+        WebSocketFeed::connect_with(&self.url, &subscribe).await
+    }
+
+    // reconnects, doubling the delay before each attempt up to the max backoff
+    async fn reconnect(self) -> Result<WebSocketFeed, Error> {
+        let mut delay = self.initial_backoff.min(self.max_backoff);
+        let mut attempts = 0;
+        loop {
+            tokio::time::sleep(delay).await;
+            attempts += 1;
+            match self.connect().await {
+                Ok(feed) => return Ok(feed),
+                Err(e) if self.max_retries.is_some_and(|n| attempts >= n) => return Err(e),
+                Err(_) => delay = delay.saturating_mul(2).min(self.max_backoff),
+            }
+        }
+    }
+}
+
+impl ReconnectingFeed {
+    /// Creates a `ReconnectingFeedBuilder` to subscribe to `channels` for `product_ids`
+    pub fn builder(product_ids: &[&str], channels: &[&str]) -> ReconnectingFeedBuilder {
+        let strings = |n: &[&str]| n.iter().map(|n| n.to_string()).collect();
+        ReconnectingFeedBuilder {
+            subscription: Subscription {
+                url: COINBASE_WEBSOCKET_URL.to_string(),
+                product_ids: strings(product_ids),
+                channels: strings(channels),
+                initial_backoff: Duration::from_secs(1),
+                max_backoff: Duration::from_secs(60),
+                max_retries: None,
+                heartbeat_timeout: None,
+                client: None,
+            },
+            has_url: false,
+        }
+    }
+
+    /// Returns when the last `heartbeat` message was received, `None` until one is received
+    /// <br>
+    /// Heartbeats are only sent when subscribed to the `heartbeat` channel
+    pub fn last_heartbeat(&self) -> Option<Instant> {
+        self.last_heartbeat
+    }
+
+    fn reconnect(&mut self) {
+        self.state = State::Reconnecting(self.subscription.clone().reconnect().boxed());
+    }
+
+    // restarts the heartbeat timeout after a message was received
+    fn reset_watchdog(&mut self) {
+        if let (Some(watchdog), Some(timeout)) =
+            (self.watchdog.as_mut(), self.subscription.heartbeat_timeout)
+        {
+            watchdog
+                .as_mut()
+                .reset(tokio::time::Instant::now() + timeout);
+        }
+    }
+
+    // whether no message has arrived within the heartbeat timeout
+    fn is_stale(&mut self, cx: &mut Context<'_>) -> bool {
+        match self.watchdog.as_mut() {
+            Some(watchdog) => watchdog.as_mut().poll(cx).is_ready(),
+            None => false,
+        }
+    }
+}
+
+impl Stream for ReconnectingFeed {
+    type Item = Result<FeedMessage, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.state {
+                State::Connected(ref mut feed) => {
+                    let message = match feed.poll_next_unpin(cx) {
+                        Poll::Ready(n) => n,
+                        Poll::Pending if self.is_stale(cx) => {
+                            self.reconnect();
+                            continue;
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    match message {
+                        Some(Ok(message)) => {
+                            self.reset_watchdog();
+                            if message["type"] == "heartbeat" {
+                                self.last_heartbeat = Some(Instant::now());
+                            }
+                            return Poll::Ready(Some(FeedMessage::from_json(message)));
+                        }
+                        // the connection was lost
+                        Some(Err(Error {
+                            kind: ErrorKind::WebSocket(_),
+                        }))
+                        | None => self.reconnect(),
+                        Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                    }
+                }
+                State::Reconnecting(ref mut reconnecting) => {
+                    let result = ready!(reconnecting.poll_unpin(cx));
+                    return Poll::Ready(Some(match result {
+                        Ok(feed) => {
+                            self.state = State::Connected(Box::new(feed));
+                            self.reset_watchdog();
+                            Ok(FeedMessage::Reconnected)
+                        }
+                        Err(e) => {
+                            self.state = State::Closed;
+                            Err(e)
+                        }
+                    }));
+                }
+                State::Closed => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// A `ReconnectingFeedBuilder` can be used to create a `ReconnectingFeed` with custom configuration.
+#[must_use = "a builder does nothing until it is connected"]
+pub struct ReconnectingFeedBuilder {
+    subscription: Subscription,
+    // whether the url was set with `sandbox` or `url`, rather than taken from the client
+    has_url: bool,
+}
+
+impl ReconnectingFeedBuilder {
+    /// Use the websocket feed of the coinbase pro sandbox
+    pub fn sandbox(mut self) -> Self {
+        self.subscription.url = COINBASE_SANDBOX_WEBSOCKET_URL.to_string();
+        self.has_url = true;
+        self
+    }
+
+    /// Use a custom websocket url, e.g. a gateway which proxies the coinbase pro websocket feed
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.subscription.url = url.into();
+        self.has_url = true;
+        self
+    }
+
+    /// Sets the delay before the first reconnection attempt, which doubles after each failed attempt up to `max` (default is 1 second, up to 60 seconds). An `initial` delay greater than `max` is lowered to `max`
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.subscription.initial_backoff = initial;
+        self.subscription.max_backoff = max;
+        self
+    }
+
+    /// Sets how many reconnection attempts in a row may fail before the stream ends (default is unlimited)
+    /// <br>
+    /// At least one attempt is made each time the connection is lost, so `0` behaves like `1`
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.subscription.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Authenticates the subscription with the API key of `client`, as required for the `user` channel
+    /// <br>
+    /// The subscribe message is signed again on each reconnection. Unless `sandbox` or `url` is called, the sandbox feed is used if `client` uses the sandbox API, like `WebSocketFeed::connect_authenticated`
+    pub fn authenticate(mut self, client: &PrivateClient) -> Self {
+        if !self.has_url {
+            self.subscription.url = if client.is_sandbox() {
+                COINBASE_SANDBOX_WEBSOCKET_URL
+            } else {
+                COINBASE_WEBSOCKET_URL
+            }
+            .to_string();
+        }
+        self.subscription.client = Some(client.clone());
+        self
+    }
+
+    /// Reconnects when no message arrives within `timeout` (default is no timeout)
+    /// <br>
+    /// A connection can go stale without being closed, subscribe to the `heartbeat` channel, on which coinbase sends a message every second, so a quiet product isn't mistaken for a stale connection
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.subscription.heartbeat_timeout = Some(timeout);
+        self
+    }
+
+    /// Connects to the websocket feed and subscribes, the first connection isn't retried
+    pub async fn connect(self) -> Result<ReconnectingFeed, Error> {
+        let feed = self.subscription.connect().await?;
+        let watchdog = self
+            .subscription
+            .heartbeat_timeout
+            .map(|n| Box::pin(tokio::time::sleep(n)));
+        Ok(ReconnectingFeed {
+            subscription: self.subscription,
+            state: State::Connected(Box::new(feed)),
+            watchdog,
+            last_heartbeat: None,
+        })
+    }
+}
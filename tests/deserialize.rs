@@ -0,0 +1,263 @@
+#![cfg(feature = "full")]
+
+use chrono::{TimeZone, Utc};
+use coinbase_client::private_client::*;
+
+// fixtures are responses captured from the coinbase pro API
+macro_rules! fixture {
+    ($name:expr) => {
+        serde_json::from_str(include_str!(concat!("fixtures/", $name))).unwrap()
+    };
+}
+
+#[test]
+fn test_deserialize_account() {
+    let account: Account = fixture!("account.json");
+    assert_eq!(account.id, "71452118-efc7-4cc4-8780-a5e22d4baa53");
+    assert_eq!(account.currency, "BTC");
+    assert_eq!(account.balance, "1.1000000000000000");
+    assert_eq!(account.available, "1.0000000000000000");
+    assert_eq!(account.hold, "0.1000000000000000");
+    assert!(account.trading_enabled);
+    assert!(account.is_consistent());
+}
+
+#[test]
+fn test_account_is_consistent() {
+    let mut account: Account = fixture!("account.json");
+    account.hold = "0.2000000000000000".to_string();
+    assert!(!account.is_consistent());
+    account.balance = "1.2".to_string();
+    assert!(account.is_consistent());
+    account.available = "-1.0".to_string();
+    assert!(!account.is_consistent());
+}
+
+#[test]
+fn test_deserialize_order_info() {
+    let orders: Vec<OrderInfo> = fixture!("orders.json");
+    let limit = &orders[0];
+    assert_eq!(limit.price.as_deref(), Some("0.10000000"));
+    assert_eq!(limit.size.as_deref(), Some("0.01000000"));
+    assert_eq!(limit.time_in_force.as_deref(), Some("GTC"));
+    assert_eq!(limit.status, "open");
+    assert_eq!(limit.done_at, None);
+    assert!(!limit.settled);
+
+    let market = &orders[1];
+    assert_eq!(market.price, None);
+    assert_eq!(market.size, None);
+    assert_eq!(market.funds.as_deref(), Some("9.9750623400000000"));
+    assert_eq!(
+        market.specified_funds.as_deref(),
+        Some("10.0000000000000000")
+    );
+    assert_eq!(
+        market.created_at,
+        Utc.ymd(2016, 12, 8).and_hms_micro(20, 9, 5, 508883)
+    );
+    assert_eq!(
+        market.done_at,
+        Some(Utc.ymd(2016, 12, 8).and_hms_milli(20, 9, 5, 527))
+    );
+    assert_eq!(market.done_reason.as_deref(), Some("filled"));
+    assert_eq!(market.reject_reason, None);
+    assert!(market.settled);
+
+    // a post-only order which would have taken liquidity is rejected
+    let rejected = &orders[2];
+    assert_eq!(rejected.status, "rejected");
+    assert_eq!(rejected.reject_reason.as_deref(), Some("post only"));
+    assert!(rejected.post_only);
+}
+
+#[test]
+fn test_deserialize_fill() {
+    let fills: Vec<Fill> = fixture!("fills.json");
+    let fill = &fills[0];
+    assert_eq!(fill.trade_id, 74);
+    assert_eq!(fill.order_id, "d50ec984-77a8-460a-b958-66f114b0de9b");
+    assert_eq!(
+        fill.created_at,
+        Utc.ymd(2014, 11, 7).and_hms_micro(22, 19, 28, 578544)
+    );
+    assert_eq!(fill.liquidity, Liquidity::Taker);
+    assert_eq!(fill.fee, "0.00025");
+    assert!(matches!(fill.side, OrderSide::Buy));
+}
+
+#[test]
+fn test_deserialize_fees() {
+    let fees: Fees = fixture!("fees.json");
+    assert_eq!(fees.maker_fee_rate, "0.0015");
+    assert_eq!(fees.taker_fee_rate, "0.0025");
+    assert_eq!(fees.usd_volume.as_deref(), Some("25000.00"));
+    // new accounts have no trailing volume
+    let fees: Fees = serde_json::from_str(
+        r#"{"maker_fee_rate": "0.0050", "taker_fee_rate": "0.0050", "usd_volume": null}"#,
+    )
+    .unwrap();
+    assert_eq!(fees.usd_volume, None);
+}
+
+#[test]
+fn test_deserialize_profile() {
+    let profiles: Vec<Profile> = fixture!("profiles.json");
+    let profile = &profiles[0];
+    assert_eq!(profile.id, "86602c68-306a-4500-ac73-4ce56a91d83c");
+    assert_eq!(profile.name, "default");
+    assert!(profile.active);
+    assert!(profile.is_default);
+    assert_eq!(
+        profile.created_at,
+        Utc.ymd(2019, 11, 18).and_hms_micro(15, 8, 40, 236309)
+    );
+}
+
+#[test]
+fn test_deserialize_report_info() {
+    let reports: Vec<ReportInfo> = fixture!("reports.json");
+    let ready = &reports[0];
+    assert_eq!(ready.r#type, "fills");
+    assert_eq!(ready.status, "ready");
+    assert_eq!(
+        ready.expires_at,
+        Some(Utc.ymd(2015, 1, 13).and_hms(10, 35, 47))
+    );
+    let params = ready.params.as_ref().unwrap();
+    assert_eq!(params.start_date, Utc.ymd(2014, 11, 1).and_hms(0, 0, 0));
+    assert_eq!(params.end_date, Utc.ymd(2014, 11, 30).and_hms(23, 59, 59));
+
+    // a pending report hasn't been created yet
+    let pending = &reports[1];
+    assert_eq!(pending.status, "pending");
+    assert_eq!(pending.created_at, None);
+    assert_eq!(pending.file_url, None);
+    assert!(pending.params.is_none());
+}
+
+#[test]
+fn test_deserialize_account_history() {
+    let history: Vec<AccountHistory> = fixture!("account_history.json");
+    let fee = &history[0];
+    assert_eq!(fee.r#type, AccountHistoryType::Fee);
+    assert_eq!(fee.amount, "-0.0025000000000000");
+    assert_eq!(
+        fee.created_at,
+        Utc.ymd(2014, 11, 7).and_hms_micro(8, 19, 27, 28459)
+    );
+    assert_eq!(fee.details.trade_id.as_deref(), Some("74"));
+    assert_eq!(fee.details.product_id.as_deref(), Some("BTC-USD"));
+
+    // transfers have no order, trade or product
+    let transfer = &history[1];
+    assert_eq!(transfer.r#type, AccountHistoryType::Transfer);
+    assert_eq!(transfer.details.order_id, None);
+    assert_eq!(transfer.details.trade_id, None);
+    assert_eq!(
+        transfer.details.transfer_id.as_deref(),
+        Some("f4e6b2b8-4b80-4c0b-9b0e-48e8f3b0f3f7")
+    );
+    assert_eq!(transfer.details.transfer_type.as_deref(), Some("deposit"));
+}
+
+#[test]
+fn test_deserialize_account_history_unknown_type() {
+    let entry: AccountHistory = serde_json::from_str(
+        r#"{
+            "id": "100",
+            "created_at": "2014-11-07T08:19:27.028459Z",
+            "amount": "0.0100000000000000",
+            "balance": "239.6690000000000000",
+            "type": "staking_reward",
+            "details": {}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(entry.r#type, AccountHistoryType::Other);
+}
+
+#[test]
+fn test_deserialize_transfer() {
+    let transfer: Transfer = serde_json::from_str(
+        r#"{
+            "id": "19ac524d-8827-4246-a1b2-18dc5ca9472c",
+            "type": "withdraw",
+            "created_at": "2019-06-18 01:37:48.78953+00",
+            "completed_at": "2019-06-18 01:37:49.756147+00",
+            "canceled_at": null,
+            "processed_at": "2019-06-18 01:37:49.756147+00",
+            "account_id": "bf091906-ca7f-499e-95fa-5bc15e918b46",
+            "user_id": "5eeac63c90b913bf3cf7c92e",
+            "amount": "40.00000000",
+            "details": {}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        transfer.created_at,
+        Utc.ymd(2019, 6, 18).and_hms_micro(1, 37, 48, 789530)
+    );
+    assert_eq!(
+        transfer.completed_at,
+        Some(Utc.ymd(2019, 6, 18).and_hms_micro(1, 37, 49, 756147))
+    );
+    assert_eq!(transfer.canceled_at, None);
+}
+
+#[test]
+fn test_deserialize_deposit_info() {
+    let deposit: DepositInfo = fixture!("deposit.json");
+    assert_eq!(deposit.amount, "10.00");
+    assert_eq!(
+        deposit.payout_at,
+        Some(Utc.ymd(2016, 8, 20).and_hms(0, 31, 9))
+    );
+    let deposit: DepositInfo = serde_json::from_str(
+        r#"{"id": "593533d2-ff31-46e0-b22e-ca754147a96a", "amount": "1.00", "currency": "BTC"}"#,
+    )
+    .unwrap();
+    assert_eq!(deposit.payout_at, None);
+}
+
+#[test]
+fn test_serialize_round_trip() {
+    let fills: Vec<Fill> = fixture!("fills.json");
+    let json = serde_json::to_string(&fills).unwrap();
+    let round_tripped: Vec<Fill> = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_tripped[0].created_at, fills[0].created_at);
+    assert_eq!(round_tripped[0].liquidity, fills[0].liquidity);
+    assert!(matches!(round_tripped[0].side, OrderSide::Buy));
+
+    let orders: Vec<OrderInfo> = fixture!("orders.json");
+    let json = serde_json::to_value(&orders).unwrap();
+    assert_eq!(json[1]["type"], "market");
+    let round_tripped: Vec<OrderInfo> = serde_json::from_value(json).unwrap();
+    assert_eq!(round_tripped[1].done_at, orders[1].done_at);
+    assert_eq!(round_tripped[0].done_at, None);
+
+    let reports: Vec<ReportInfo> = fixture!("reports.json");
+    let round_tripped: Vec<ReportInfo> =
+        serde_json::from_str(&serde_json::to_string(&reports).unwrap()).unwrap();
+    assert_eq!(round_tripped[0].expires_at, reports[0].expires_at);
+}
+
+#[test]
+fn test_deserialize_withdraw_info() {
+    let withdrawal: WithdrawInfo = fixture!("withdrawal.json");
+    assert_eq!(withdrawal.amount, "9.99");
+    assert_eq!(withdrawal.fee.as_deref(), Some("0.01"));
+    assert_eq!(withdrawal.subtotal.as_deref(), Some("10.00"));
+    assert_eq!(withdrawal.payout_at, None);
+
+    // withdrawals to a payment method have a payout date but no fee
+    let withdrawal: WithdrawInfo = serde_json::from_str(
+        r#"{"id": "593533d2-ff31-46e0-b22e-ca754147a96a", "amount": "10.00", "currency": "USD", "payout_at": "2016-08-20T00:31:09Z"}"#,
+    )
+    .unwrap();
+    assert_eq!(withdrawal.fee, None);
+    assert_eq!(
+        withdrawal.payout_at,
+        Some(Utc.ymd(2016, 8, 20).and_hms(0, 31, 9))
+    );
+}
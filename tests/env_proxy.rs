@@ -0,0 +1,48 @@
+#![cfg(feature = "full")]
+
+// sets proxy environment variables, so this is its own test binary rather than a module of `mod.rs`
+// and runs on a current thread runtime, so no other thread reads the environment while it is changed
+
+use coinbase_client::public_client::PublicClient;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn test_env_proxy() {
+    // a proxy which answers a single request itself
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    std::env::set_var(
+        "HTTP_PROXY",
+        format!("http://{}", listener.local_addr().unwrap()),
+    );
+    std::env::remove_var("NO_PROXY");
+    std::env::remove_var("no_proxy");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let n = socket.read(&mut request).await.unwrap();
+        let body = r#"{"iso": "2015-01-07T23:47:25.201Z", "epoch": 1420674445.201}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..n]).to_string()
+    });
+
+    // without opting in the proxy is ignored, so the unresolvable host fails
+    let client = PublicClient::builder()
+        .url("http://api.example.invalid")
+        .build()
+        .unwrap();
+    assert!(client.get_time().await.is_err());
+
+    let client = PublicClient::builder()
+        .url("http://api.example.invalid")
+        .env_proxy()
+        .build()
+        .unwrap();
+    let _time = client.get_time().await.unwrap();
+    let request = server.await.unwrap();
+    assert!(request.starts_with("GET http://api.example.invalid/time"));
+}
@@ -0,0 +1,70 @@
+use coinbase_client::error::*;
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn test_error_is_send_sync() {
+    assert_send_sync::<Error>();
+    assert_send_sync::<ErrorKind>();
+}
+
+#[test]
+fn test_is_auth_error() {
+    let unauthorized = Error::new(ErrorKind::Status(StatusError::new(
+        401,
+        "invalid signature".to_string(),
+    )));
+    let forbidden = Error::new(ErrorKind::Status(StatusError::new(
+        403,
+        "Forbidden".to_string(),
+    )));
+    let not_found = Error::new(ErrorKind::Status(StatusError::new(
+        404,
+        "NotFound".to_string(),
+    )));
+    assert!(unauthorized.is_auth_error());
+    assert!(forbidden.is_auth_error());
+    assert!(!not_found.is_auth_error());
+    assert!(!Error::new(ErrorKind::Timeout).is_auth_error());
+}
+
+#[test]
+fn test_unauthorized_display_includes_hint() {
+    let unauthorized = Error::new(ErrorKind::Status(StatusError::new(
+        401,
+        "invalid signature".to_string(),
+    )));
+    let message = unauthorized.to_string();
+    assert!(message.contains("invalid signature"));
+    assert!(message.contains("system clock"));
+}
+
+#[test]
+fn test_status_code() {
+    let rate_limited = Error::new(ErrorKind::Status(StatusError::new(
+        429,
+        "Rate limit exceeded".to_string(),
+    )));
+    assert_eq!(rate_limited.status_code(), Some(429));
+    assert!(rate_limited.is_rate_limited());
+    let not_found = Error::new(ErrorKind::Status(StatusError::new(
+        404,
+        "NotFound".to_string(),
+    )));
+    assert_eq!(not_found.status_code(), Some(404));
+    assert!(!not_found.is_rate_limited());
+    assert_eq!(Error::new(ErrorKind::Timeout).status_code(), None);
+}
+
+#[test]
+fn test_kind() {
+    let not_found = Error::new(ErrorKind::Status(StatusError::new(
+        404,
+        "NotFound".to_string(),
+    )));
+    assert!(matches!(not_found.kind(), ErrorKind::Status(err) if err.code == 404));
+    match not_found.into_kind() {
+        ErrorKind::Status(err) => assert_eq!(err.message, "NotFound"),
+        _ => panic!("expected a status error"),
+    }
+}
@@ -0,0 +1,85 @@
+use coinbase_client::round_to_increment;
+
+#[test]
+fn test_round_to_increment() {
+    assert_eq!(
+        round_to_increment("0.123456789", "0.00000001").unwrap(),
+        "0.12345678"
+    );
+    assert_eq!(
+        round_to_increment("36000.129", "0.01000000").unwrap(),
+        "36000.12"
+    );
+    assert_eq!(round_to_increment("10.75", "0.5").unwrap(), "10.5");
+    assert_eq!(round_to_increment("5", "0.01").unwrap(), "5.00");
+    assert_eq!(round_to_increment("0.009", "0.01").unwrap(), "0.00");
+    assert_eq!(round_to_increment("123.9", "1").unwrap(), "123");
+    assert_eq!(round_to_increment(".5", "0.1").unwrap(), "0.5");
+}
+
+#[test]
+fn test_round_to_increment_scientific_notation() {
+    assert_eq!(
+        round_to_increment("0.123456789123", "1.5e-10").unwrap(),
+        "0.12345678900"
+    );
+    assert_eq!(
+        round_to_increment("0.123456789123", "1.0E-8").unwrap(),
+        "0.12345678"
+    );
+    assert_eq!(round_to_increment("12345.6", "1.5e3").unwrap(), "12000");
+    assert_eq!(
+        round_to_increment("12345678901", "1.0e10").unwrap(),
+        "10000000000"
+    );
+}
+
+#[test]
+fn test_round_to_increment_invalid() {
+    assert!(round_to_increment("abc", "0.01").is_err());
+    assert!(round_to_increment("-1.5", "0.01").is_err());
+    assert!(round_to_increment("1.5", "0").is_err());
+    assert!(round_to_increment("1.5", "0.000").is_err());
+    assert!(round_to_increment("1.5", "").is_err());
+}
+
+#[cfg(feature = "full")]
+#[test]
+fn test_format_amount() {
+    use coinbase_client::format_amount;
+    use coinbase_client::public_client::Currency;
+
+    let currency: Currency = serde_json::from_str(
+        r#"{
+            "id": "BTC",
+            "name": "Bitcoin",
+            "min_size": "0.00000001",
+            "status": "online",
+            "message": "",
+            "max_precision": "0.00000001",
+            "convertible_to": [],
+            "details": {
+                "type": "crypto",
+                "symbol": "₿",
+                "network_confirmations": 3,
+                "sort_order": 3,
+                "crypto_address_link": "https://live.blockcypher.com/btc/address/{{address}}",
+                "crypto_transaction_link": "https://live.blockcypher.com/btc/tx/{{txId}}",
+                "push_payment_methods": ["crypto"],
+                "min_withdrawal_amount": 0.0001,
+                "max_withdrawal_amount": 2400
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        format_amount("0.123456789", &currency).unwrap(),
+        "0.12345678 BTC"
+    );
+    assert_eq!(format_amount("2", &currency).unwrap(), "2.00000000 BTC");
+    assert_eq!(
+        format_amount("-0.0025000000000000", &currency).unwrap(),
+        "-0.00250000 BTC"
+    );
+    assert!(format_amount("abc", &currency).is_err());
+}
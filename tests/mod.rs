@@ -1,2 +1,7 @@
+mod deserialize;
+mod error;
+mod increment;
 mod private_client;
-mod public_client;
\ No newline at end of file
+mod product_id;
+mod public_client;
+mod websocket;
\ No newline at end of file
@@ -1,6 +1,9 @@
+#![cfg(feature = "full")]
+
 use coinbase_client::private_client::*;
 use dotenv;
 use std::env;
+use std::time::Duration;
 
 fn create_client() -> PrivateClient {
     dotenv::from_filename(".env").expect("error reading .env file");
@@ -9,12 +12,43 @@ fn create_client() -> PrivateClient {
     let key = env::var("KEY").expect("Cant find api key");
     PrivateClient::new_sandbox(secret, passphrase, key)
 }
+
+// a client for tests of parameters which are rejected before a request is sent, so no credentials are needed
+fn create_offline_client() -> PrivateClient {
+    PrivateClient::new_sandbox(
+        "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==",
+        "t9riylyad0r",
+        "4a9f6de8bcdee641a0a207613dfb43ef",
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_sync_time() {
+    let client = create_client();
+    assert!(client.clock_skew().is_none());
+    client.sync_time().await.unwrap();
+    assert!(client.clock_skew().is_some());
+    let _accounts = client.get_accounts().await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_accounts() {
     let client = create_client();
     let _accounts = client.get_accounts().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_profile_balances() {
+    let client = create_client();
+    let balances = client
+        .get_profile_balances("e1d7731f-b7e2-4285-b711-eeec76fc2aff")
+        .await
+        .unwrap();
+    assert!(balances
+        .iter()
+        .all(|account| account.profile_id == "e1d7731f-b7e2-4285-b711-eeec76fc2aff"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_account() {
     let client = create_client();
@@ -24,6 +58,16 @@ async fn test_get_account() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_account_with_currency() {
+    let client = create_client();
+    let (account, currency) = client
+        .get_account_with_currency("0589d87c-154d-4f5b-9ed9-ff814f70e04a")
+        .await
+        .unwrap();
+    assert_eq!(account.currency, currency.id);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_place_order_market_funds() {
     let order = OrderBuilder::market(
@@ -51,14 +95,54 @@ async fn test_place_order_limit() {
     let _res = client.place_order(order).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_place_orders() {
+    let orders = vec![
+        OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0").build(),
+        // rejected, which must not fail the other orders
+        OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "0.0").build(),
+        OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0").build(),
+    ];
+    let client = create_client();
+    let order_ids = client.place_orders(orders, 2).await;
+    assert_eq!(order_ids.len(), 3);
+    assert!(order_ids[0].is_ok());
+    assert!(order_ids[1].is_err());
+    assert!(order_ids[2].is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_place_order_profile_id() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0")
+        .profile_id("e1d7731f-b7e2-4285-b711-eeec76fc2aff")
+        .build();
+    let client = create_client();
+    let _res = client.place_order(order).await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_place_order_stop() {
     let order = OrderBuilder::stop(
         OrderSide::Buy,
         "BTC-USD",
-        "36000.0",
+        "38000.0",
         "1.0",
         "37000.0",
+        OrderStop::Entry,
+    )
+    .build();
+    let client = create_client();
+    let _res = client.place_order(order).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_place_order_stop_limit() {
+    let order = OrderBuilder::stop_limit(
+        OrderSide::Sell,
+        "BTC-USD",
+        "29000.0",
+        "1.0",
+        "30000.0",
         OrderStop::Loss,
     )
     .build();
@@ -66,6 +150,67 @@ async fn test_place_order_stop() {
     let _res = client.place_order(order).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_place_order_stop_market() {
+    let order = OrderBuilder::stop_market(
+        OrderSide::Buy,
+        "BTC-USD",
+        SizeOrFunds::Funds("10.00".to_owned()),
+        "40000.0",
+        OrderStop::Entry,
+    )
+    .build();
+    let client = create_client();
+    let _res = client.place_order(order).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_place_order_full() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+    let client = create_client();
+    let order = client.place_order_full(order).await.unwrap();
+    assert_eq!(order.product_id, "BTC-USD");
+    assert_eq!(order.price.as_deref(), Some("33000.00000000"));
+    let _res = client.cancel_order(&order.id).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_order_with_fills() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+    let client = create_client();
+    let order_id = client.place_order(order).await.unwrap();
+    let (order, fills) = client.get_order_with_fills(&order_id).await.unwrap();
+    assert_eq!(order.id, order_id);
+    assert!(fills.iter().all(|fill| fill.order_id == order_id));
+    let _res = client.cancel_order(&order_id).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_order_checked() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+    let client = create_client();
+    let order_id = client.place_order(order).await.unwrap();
+    let outcome = client.cancel_order_checked(&order_id).await.unwrap();
+    assert_eq!(
+        outcome,
+        CancelOutcome::Cancelled {
+            id: order_id.clone()
+        }
+    );
+    let outcome = client.cancel_order_checked(&order_id).await.unwrap();
+    assert_eq!(outcome, CancelOutcome::AlreadyDone);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_order_by_oid_checked_invalid_oid() {
+    let client = create_offline_client();
+    let outcome = client.cancel_order_by_oid_checked("my-order").await;
+    assert!(matches!(
+        outcome.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_cancel_order() {
     let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
@@ -80,6 +225,25 @@ async fn test_cancel_orders() {
     let _canceled_orders_ids = client.cancel_orders().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_orders_checked() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+    let client = create_client();
+    let order_id = client.place_order(order).await.unwrap();
+    let result = client.cancel_orders_checked().await.unwrap();
+    assert!(result.cancelled.contains(&order_id));
+    assert!(!result.not_cancelled.contains(&order_id));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cancel_all_and_wait() {
+    let client = create_client();
+    client
+        .cancel_all_and_wait(Duration::from_secs(10))
+        .await
+        .unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_orders() {
     let client = create_client();
@@ -94,6 +258,197 @@ async fn test_get_orders() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_orders_sorted() {
+    let client = create_client();
+    let orders = client
+        .get_orders_sorted(
+            Some(OrderStatus::OpenActivePending),
+            OrderSortedBy::CreatedAt,
+            Sorting::Desc,
+            Some(20),
+        )
+        .await
+        .unwrap();
+    assert!(orders.len() <= 20);
+    assert!(orders
+        .windows(2)
+        .all(|pair| pair[0].created_at >= pair[1].created_at));
+}
+
+#[test]
+fn test_parse_order_enums() {
+    use std::convert::TryFrom;
+
+    assert_eq!("buy".parse::<OrderSide>().unwrap(), OrderSide::Buy);
+    assert_eq!(OrderSide::try_from("sell").unwrap(), OrderSide::Sell);
+    assert!("BUY".parse::<OrderSide>().is_err());
+    assert!(matches!(
+        "GTC".parse::<TimeInForce>().unwrap(),
+        TimeInForce::GoodTillCancel { post_only: false }
+    ));
+    assert!(matches!(
+        TimeInForce::try_from("FOK").unwrap(),
+        TimeInForce::FillOrKill
+    ));
+    assert!("GTT".parse::<TimeInForce>().is_err());
+    assert!(matches!(
+        "pending".parse::<OrderStatus>().unwrap(),
+        OrderStatus::Pending
+    ));
+    let err = OrderStatus::try_from("done").unwrap_err();
+    assert!(matches!(
+        err.kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
+#[test]
+fn test_order_round_to_product() {
+    let product: coinbase_client::public_client::Product = serde_json::from_str(
+        r#"{
+            "id": "BTC-USD",
+            "display_name": "BTC/USD",
+            "base_currency": "BTC",
+            "quote_currency": "USD",
+            "base_increment": "0.00000001",
+            "quote_increment": "0.01000000",
+            "base_min_size": "0.00100000",
+            "base_max_size": "280.00000000",
+            "min_market_funds": "5",
+            "max_market_funds": "1000000",
+            "status": "online",
+            "status_message": "",
+            "cancel_only": false,
+            "limit_only": false,
+            "post_only": false,
+            "trading_disabled": false
+        }"#,
+    )
+    .unwrap();
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.129", "0.123456789")
+        .round_to_product(&product)
+        .build();
+    let order = serde_json::to_value(order).unwrap();
+    assert_eq!(order["price"], "36000.12");
+    assert_eq!(order["size"], "0.12345678");
+}
+
+#[test]
+fn test_serialize_limit_order() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.00", "0.01")
+        .time_in_force(TimeInForce::GoodTillTime {
+            cancel_after: CancelAfter::Hour,
+            post_only: true,
+        })
+        .self_trade_prevention(SelfTradePrevention::CancelOldest)
+        .client_oid("8ac4ec34-3a9e-4b2a-a9b4-1a3b4ff2a3c5".to_string())
+        .build();
+    assert_eq!(
+        serde_json::to_value(order).unwrap(),
+        serde_json::json!({
+            "type": "limit",
+            "side": "buy",
+            "product_id": "BTC-USD",
+            "price": "36000.00",
+            "size": "0.01",
+            "time_in_force": "GTT",
+            "cancel_after": "hour",
+            "post_only": true,
+            "stp": "co",
+            "client_oid": "8ac4ec34-3a9e-4b2a-a9b4-1a3b4ff2a3c5"
+        })
+    );
+}
+
+#[test]
+fn test_serialize_market_order() {
+    let by_size = OrderBuilder::market(
+        OrderSide::Sell,
+        "BTC-USD",
+        SizeOrFunds::Size("0.01".to_string()),
+    )
+    .build();
+    assert_eq!(
+        serde_json::to_value(by_size).unwrap(),
+        serde_json::json!({
+            "type": "market",
+            "side": "sell",
+            "product_id": "BTC-USD",
+            "size": "0.01"
+        })
+    );
+    let by_funds = OrderBuilder::market(
+        OrderSide::Buy,
+        "BTC-USD",
+        SizeOrFunds::Funds("10.00".to_string()),
+    )
+    .build();
+    assert_eq!(
+        serde_json::to_value(by_funds).unwrap(),
+        serde_json::json!({
+            "type": "market",
+            "side": "buy",
+            "product_id": "BTC-USD",
+            "funds": "10.00"
+        })
+    );
+}
+
+#[test]
+fn test_market_order_has_size_or_funds() {
+    let by_size = serde_json::to_value(
+        OrderBuilder::market_with_size(OrderSide::Sell, "BTC-USD", "0.01").build(),
+    )
+    .unwrap();
+    assert_eq!(by_size["size"], "0.01");
+    assert!(by_size.get("funds").is_none());
+    let by_funds = serde_json::to_value(
+        OrderBuilder::market_with_funds(OrderSide::Buy, "BTC-USD", "10.00").build(),
+    )
+    .unwrap();
+    assert_eq!(by_funds["funds"], "10.00");
+    assert!(by_funds.get("size").is_none());
+    let stop = serde_json::to_value(
+        OrderBuilder::stop_market(
+            OrderSide::Sell,
+            "BTC-USD",
+            SizeOrFunds::Funds("10.00".to_string()),
+            "30000.00",
+            OrderStop::Loss,
+        )
+        .build(),
+    )
+    .unwrap();
+    assert_eq!(stop["funds"], "10.00");
+    assert!(stop.get("size").is_none());
+}
+
+#[test]
+fn test_serialize_stop_order() {
+    let order = OrderBuilder::stop_limit(
+        OrderSide::Sell,
+        "BTC-USD",
+        "29000.00",
+        "0.01",
+        "30000.00",
+        OrderStop::Loss,
+    )
+    .build();
+    assert_eq!(
+        serde_json::to_value(order).unwrap(),
+        serde_json::json!({
+            "type": "limit",
+            "side": "sell",
+            "product_id": "BTC-USD",
+            "price": "29000.00",
+            "size": "0.01",
+            "stop": "loss",
+            "stop_price": "30000.00"
+        })
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_order() {
     let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "36000.0", "1.0").build();
@@ -102,6 +457,33 @@ async fn test_get_order() {
     let _order = client.get_order(&order_id).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_order_and_wait_settled() {
+    let order = OrderBuilder::market(
+        OrderSide::Buy,
+        "BTC-USD",
+        SizeOrFunds::Funds("10.00".to_string()),
+    )
+    .build();
+    let client = create_client();
+    let order_id = client.place_order(order).await.unwrap();
+    let order = client
+        .get_order_and_wait_settled(&order_id, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert!(order.settled);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_try_get_order_not_found() {
+    let client = create_client();
+    let order = client
+        .try_get_order("00000000-0000-0000-0000-000000000000")
+        .await
+        .unwrap();
+    assert!(order.is_none());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_fill_by_order_id() {
     let client = create_client();
@@ -121,12 +503,167 @@ async fn test_get_fills_by_product_id() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_recent_fills() {
+    let client = create_client();
+    let fills = client
+        .get_recent_fills(&["BTC-USD", "ETH-USD"])
+        .await
+        .unwrap();
+    assert!(fills
+        .windows(2)
+        .all(|pair| pair[0].created_at >= pair[1].created_at));
+}
+
+// a server which answers each request with the next page of fills and its `CB-AFTER` cursor, returning the requests it received
+async fn serve_fill_pages(
+    pages: Vec<(&'static str, Option<u64>)>,
+) -> (PrivateClient, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for (body, after) in pages {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            requests.push(String::from_utf8_lossy(&request[..n]).to_string());
+            let cursor = after.map_or(String::new(), |n| format!("cb-after: {}\r\n", n));
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n{}content-length: {}\r\n\r\n{}",
+                cursor,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+    let client = PrivateClient::builder(
+        "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==",
+        "t9riylyad0r",
+        "4a9f6de8bcdee641a0a207613dfb43ef",
+    )
+    .url(url)
+    .build()
+    .unwrap();
+    (client, server)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_all_fills_for_product() {
+    // two pages of fills followed by an empty page
+    let fills = include_str!("fixtures/fills.json");
+    let (client, server) =
+        serve_fill_pages(vec![(fills, Some(73)), (fills, Some(72)), ("[]", None)]).await;
+    let fills = client
+        .get_all_fills_for_product("BTC-USD", 10)
+        .await
+        .unwrap();
+    assert_eq!(fills.len(), 2);
+    let requests = server.await.unwrap();
+    assert!(requests[0].starts_with("GET /fills?product_id=BTC-USD&limit=100 "));
+    assert!(requests[1].starts_with("GET /fills?product_id=BTC-USD&after=73&limit=100 "));
+    assert!(requests[2].starts_with("GET /fills?product_id=BTC-USD&after=72&limit=100 "));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_all_fills_for_product_exactly_max_pages() {
+    // the empty page ending the history doesn't count against max_pages
+    let fills = include_str!("fixtures/fills.json");
+    let (client, server) =
+        serve_fill_pages(vec![(fills, Some(73)), (fills, Some(72)), ("[]", None)]).await;
+    let fills = client
+        .get_all_fills_for_product("BTC-USD", 2)
+        .await
+        .unwrap();
+    assert_eq!(fills.len(), 2);
+    assert_eq!(server.await.unwrap().len(), 3);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_all_fills_for_product_max_pages() {
+    let fills = include_str!("fixtures/fills.json");
+    let (client, server) = serve_fill_pages(vec![(fills, Some(73)), (fills, Some(72))]).await;
+    let error = client
+        .get_all_fills_for_product("BTC-USD", 1)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.kind,
+        coinbase_client::error::ErrorKind::TooManyPages(1)
+    ));
+    assert_eq!(server.await.unwrap().len(), 2);
+
+    let error = create_offline_client()
+        .get_all_fills_for_product("BTC-USD", 0)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_product_fill_stats() {
+    let client = create_client();
+    let _stats = client.get_product_fill_stats("BTC-USD").await.unwrap();
+}
+
+#[test]
+fn test_fill_stats_from_fills() {
+    let fills: Vec<Fill> = serde_json::from_str(
+        r#"[
+            {
+                "trade_id": 74,
+                "product_id": "BTC-USD",
+                "price": "10.00",
+                "size": "0.01",
+                "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                "created_at": "2014-11-07T22:19:28.578544Z",
+                "liquidity": "T",
+                "fee": "0.00025",
+                "settled": true,
+                "side": "buy"
+            },
+            {
+                "trade_id": 75,
+                "product_id": "BTC-USD",
+                "price": "10.00",
+                "size": "0.02",
+                "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+                "created_at": "2014-11-07T22:19:29.578544Z",
+                "liquidity": "M",
+                "fee": "0.00075",
+                "settled": true,
+                "side": "sell"
+            }
+        ]"#,
+    )
+    .unwrap();
+    let stats = FillStats::from_fills(&fills).unwrap();
+    assert_eq!(stats.maker_count, 1);
+    assert_eq!(stats.taker_count, 1);
+    assert!((stats.total_fees - 0.001).abs() < 1e-12);
+    assert!((stats.buy_volume - 0.01).abs() < 1e-12);
+    assert!((stats.sell_volume - 0.02).abs() < 1e-12);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_limits() {
     let client = create_client();
     let _limits = client.get_limits().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_can_withdraw() {
+    let client = create_client();
+    let _can_withdraw = client.can_withdraw("ADA", "6.0").await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_deposits() {
     let client = create_client();
@@ -141,6 +678,21 @@ async fn test_get_deposits() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_limit_greater_than_100() {
+    let client = create_offline_client();
+    let deposits = client.get_deposits(None, None, None, Some(500)).await;
+    assert!(matches!(
+        deposits.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+    let withdrawals = client.get_withdrawals(None, None, None, Some(101)).await;
+    assert!(matches!(
+        withdrawals.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_internal_deposits() {
     let client = create_client();
@@ -175,6 +727,17 @@ async fn test_get_coinbase_accounts() {
     let _accounts = client.get_coinbase_accounts().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_coinbase_account_by_currency() {
+    let client = create_client();
+    let account = client
+        .get_coinbase_account_by_currency("BTC")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.currency, "BTC");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_deposit_funds() {
     let client = create_client();
@@ -188,11 +751,30 @@ async fn test_deposit_funds() {
 async fn test_deposit_from_coinbase() {
     let client = create_client();
     let _deposit = client
-        .deposit_funds_from_coinbase(13.468564, "ALGO", "2141660b-da3d-5060-8af1-b8478cf6dd44")
+        .deposit_funds_from_coinbase("13.468564", "ALGO", "2141660b-da3d-5060-8af1-b8478cf6dd44")
         .await
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transfer_invalid_amount() {
+    let client = create_offline_client();
+    let deposit = client
+        .deposit_funds("1e-7", "BTC", "9da3e279-20a1-57e4-95f8-52ec41041999")
+        .await;
+    assert!(matches!(
+        deposit.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+    let withdrawal = client
+        .withdraw_funds("-5.00", "USD", "9da3e279-20a1-57e4-95f8-52ec41041999")
+        .await;
+    assert!(matches!(
+        withdrawal.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_generate_crypto_address() {
     let client = create_client();
@@ -239,6 +821,14 @@ async fn test_get_withdrawal() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_full_account_snapshot() {
+    let client = create_client();
+    let accounts = client.get_accounts().await.unwrap();
+    let snapshot = client.get_full_account_snapshot().await.unwrap();
+    assert_eq!(snapshot.len(), accounts.len());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_account_history() {
     let client = create_client();
@@ -248,6 +838,14 @@ async fn test_get_account_history() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_portfolio_value() {
+    let client = create_client();
+    let portfolio = client.portfolio_value("USD").await.unwrap();
+    assert!(portfolio.value >= 0.0);
+    assert!(!portfolio.unpriced.iter().any(|n| n == "USD"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_account_holds() {
     let client = create_client();
@@ -262,12 +860,67 @@ async fn test_get_account_holds() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_resolve_hold() {
+    let order = OrderBuilder::limit(OrderSide::Buy, "BTC-USD", "33000.0", "1.0").build();
+    let client = create_client();
+    let order_id = client.place_order(order).await.unwrap();
+    let accounts = client.get_accounts().await.unwrap();
+    let usd = accounts.iter().find(|n| n.currency == "USD").unwrap();
+    let holds = client
+        .get_account_holds(&usd.id, None, None, None)
+        .await
+        .unwrap();
+    let hold = holds.iter().find(|n| n.r#ref == order_id).unwrap();
+    match client.resolve_hold(hold).await.unwrap() {
+        HoldResolution::Order(order) => assert_eq!(order.id, order_id),
+        HoldResolution::Transfer(_) => panic!("expected the hold of an order"),
+    }
+    let _res = client.cancel_order(&order_id).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_account_transfers() {
+    let client = create_client();
+    let _transfers = client
+        .get_account_transfers(
+            "680f85f4-1a99-4108-93ce-a9066f9de246",
+            None,
+            None,
+            Some(100),
+        )
+        .await
+        .unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_fees() {
     let client = create_client();
     let _fees = client.get_fees().await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_watch_fees() {
+    use futures::StreamExt;
+    let client = create_client();
+    let mut fees = Box::pin(client.watch_fees(Duration::from_secs(1)));
+    let _fees = fees.next().await.unwrap().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_current_fee_tier() {
+    let client = create_client();
+    let _tier = client.current_fee_tier().await.unwrap();
+}
+
+#[test]
+fn test_fee_tier_for_usd_volume() {
+    assert_eq!(FeeTier::for_usd_volume(0.0).name, "$0 - $10K");
+    assert_eq!(FeeTier::for_usd_volume(75_000.0).name, "$50K - $100K");
+    assert_eq!(FeeTier::for_usd_volume(100_000.0).name, "$100K - $1M");
+    assert_eq!(FeeTier::for_usd_volume(2_000_000_000.0).name, "$1B+");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_fee_estimate_() {
     let client = create_client();
@@ -281,11 +934,36 @@ async fn test_get_fee_estimate_() {
 async fn test_stablecoin_conversion() {
     let client = create_client();
     let _conversion = client
-        .convert_stablecoin("USD", "USDC", 10.00)
+        .convert_stablecoin("USD", "USDC", "10.00")
         .await
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_stablecoin_conversion_with_options() {
+    let client = create_client();
+    let _conversion = client
+        .convert_stablecoin_with_options(
+            "USD",
+            "USDC",
+            "10.00",
+            Some("b7482eaa-3eea-4065-9d81-1484257c5f92"),
+            Some("1d4d2ad2-a5ce-4b28-ad2c-1b0a4b21a2d5"),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_stablecoin_conversion_invalid_amount() {
+    let client = create_offline_client();
+    let conversion = client.convert_stablecoin("USD", "USDC", "1e1").await;
+    assert!(matches!(
+        conversion.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_report() {
     let client = create_client();
@@ -300,6 +978,15 @@ async fn test_report() {
     let _response = client.create_report(report).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_balance_report() {
+    let client = create_client();
+    let report = ReportBuilder::balance("2014-11-01T00:00:00.000Z", "2021-06-11T02:48:15.853Z")
+        .format(Format::CSV)
+        .build();
+    let _response = client.create_report(report).await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_report() {
     let client = create_client();
@@ -309,10 +996,36 @@ async fn test_get_report() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_reports() {
+    let client = create_client();
+    let reports = client
+        .get_reports(Some(ReportType::Fills), Some(true))
+        .await
+        .unwrap();
+    assert!(reports.iter().all(|n| n.r#type == "fills"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_download_report() {
+    let client = create_client();
+    let report = client
+        .get_report("d4a3e847-b618-454d-bcb3-e77b0ad61600")
+        .await
+        .unwrap();
+    let _file = client.download_report(&report).await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_profiles() {
     let client = create_client();
-    let _profiles = client.get_profiles().await.unwrap();
+    let _profiles = client.get_profiles(None).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_active_profiles() {
+    let client = create_client();
+    let _profiles = client.get_profiles(Some(true)).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -327,29 +1040,69 @@ async fn test_get_profile() {
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_create_profile_transfer() {
     let client = create_client();
-    let ok = client
+    let transfer = client
         .create_profile_transfer(
             "e1d7731f-b7e2-4285-b711-eeec76fc2aff",
             "3510ac37-1a99-4c9c-9865-15f1bc5a832e",
             "USD",
-            10.00,
+            "10.00",
         )
         .await
         .unwrap();
-    assert_eq!(ok, "OK")
+    assert_eq!(transfer.currency, "USD");
+    assert_eq!(transfer.amount, "10.00");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_check_crypto_withdrawal() {
+    let client = create_client();
+    client.check_crypto_withdrawal("6.0", "ADA").await.unwrap();
+    let err = client
+        .check_crypto_withdrawal("0.00000001", "BTC")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_withdraw_to_crypto_address() {
     let client = create_client();
-    let _res = client.withdraw_to_crypto_address(6.0, "ADA", "addr1qyk0yr3ht9d6hcqwp8q8j38nxs04npyjauzz9wp5jcfr95h64lvegfk57zmzltj3nmpjff6490ayyvjh0g6sne6hm3hspnnscy", None, None, None).await.unwrap();
+    let _res = client.withdraw_to_crypto_address("6.0", "ADA", "addr1qyk0yr3ht9d6hcqwp8q8j38nxs04npyjauzz9wp5jcfr95h64lvegfk57zmzltj3nmpjff6490ayyvjh0g6sne6hm3hspnnscy", None, None, None).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_withdraw_to_coinbase() {
     let client = create_client();
     let _res = client
-        .withdraw_to_coinbase(1.0, "ADA", "91bdfea7-f243-5baa-bb0d-5b93c9f09ffc")
+        .withdraw_to_coinbase("1.0", "ADA", "91bdfea7-f243-5baa-bb0d-5b93c9f09ffc")
         .await
         .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_signed_prices() {
+    let client = create_client();
+    let _prices = client.get_signed_prices().await.unwrap();
+}
+
+#[test]
+fn test_decode_signed_price() {
+    let message = "0x0000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000005e5da3b400000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000209a333280000000000000000000000000000000000000000000000000000000000000006707269636573000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000034254430000000000000000000000000000000000000000000000000000000000";
+    let price = SignedPrice::decode(message, "0xsignature").unwrap();
+    assert_eq!(price.kind, "prices");
+    assert_eq!(price.timestamp, 1583195060);
+    assert_eq!(price.key, "BTC");
+    assert_eq!(price.value, 8751625000);
+    assert_eq!(price.signature, "0xsignature");
+    assert!(SignedPrice::decode("0x1234", "0xsignature").is_err());
+    // an offset near usize::MAX is rejected rather than overflowing
+    let message = format!("0x{}{}{}", "00".repeat(24), "ff".repeat(8), "00".repeat(96));
+    let err = SignedPrice::decode(&message, "0xsignature").unwrap_err();
+    assert!(matches!(
+        err.kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
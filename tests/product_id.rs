@@ -0,0 +1,17 @@
+use coinbase_client::ProductId;
+
+#[test]
+fn test_parse_product_id() {
+    let product_id: ProductId = "BTC-USD".parse().unwrap();
+    assert_eq!(product_id.base(), "BTC");
+    assert_eq!(product_id.quote(), "USD");
+    assert_eq!(product_id.to_string(), "BTC-USD");
+}
+
+#[test]
+fn test_parse_invalid_product_id() {
+    assert!("BTCUSD".parse::<ProductId>().is_err());
+    assert!("BTC-".parse::<ProductId>().is_err());
+    assert!("-USD".parse::<ProductId>().is_err());
+    assert!("BTC-USD-EUR".parse::<ProductId>().is_err());
+}
@@ -1,10 +1,25 @@
+#![cfg(feature = "full")]
+
 use coinbase_client::public_client::*;
+use futures::StreamExt;
+use std::time::Duration;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_products() {
     let client = PublicClient::new_sandbox();
     let _products = client.get_products().await.unwrap();
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_builder() {
+    let client = PublicClient::builder()
+        .sandbox()
+        .pool_idle_timeout(Duration::from_secs(300))
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+    let _products = client.get_products().await.unwrap();
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_product() {
     let client = PublicClient::new_sandbox();
@@ -16,6 +31,22 @@ async fn test_get_product_order_book_all() {
     let _order_book = client.get_product_order_book_all("BTC-USD").await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_best_bid_ask() {
+    let client = PublicClient::new_sandbox();
+    let (bid, ask) = client.best_bid_ask("BTC-USD").await.unwrap();
+    assert!(bid.price.parse::<f64>().unwrap() <= ask.price.parse::<f64>().unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_market_snapshot() {
+    let client = PublicClient::new_sandbox();
+    let snapshot = client.get_market_snapshot("BTC-USD").await.unwrap();
+    assert!(snapshot.book.bids.len() <= 1);
+    assert!(snapshot.book.asks.len() <= 1);
+    assert!(!snapshot.stats.last.is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_product_order_book_top50() {
     let client = PublicClient::new_sandbox();
@@ -40,6 +71,38 @@ async fn test_get_product_ticker() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_tickers() {
+    let client = PublicClient::new_sandbox();
+    let tickers = client.get_tickers(&["BTC-USD", "ETH-USD"]).await;
+    assert!(tickers["BTC-USD"].is_ok());
+    assert!(tickers["ETH-USD"].is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_ticker_stream() {
+    let client = PublicClient::new_sandbox();
+    let tickers: Vec<_> = client
+        .ticker_stream("BTC-USD", Duration::from_millis(500))
+        .take(2)
+        .collect()
+        .await;
+    assert_eq!(tickers.len(), 2);
+    assert!(tickers.iter().all(|ticker| ticker.is_ok()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_product_trades_limit_greater_than_100() {
+    let client = PublicClient::new_sandbox();
+    let trades = client
+        .get_product_trades("BTC-USD", None, None, Some(500))
+        .await;
+    assert!(matches!(
+        trades.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_product_trades() {
     let client = PublicClient::new_sandbox();
@@ -58,12 +121,82 @@ async fn test_get_product_historic_rates() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_cache_ttl() {
+    let client = PublicClient::builder()
+        .sandbox()
+        .cache_ttl(Duration::from_secs(60))
+        .build()
+        .unwrap();
+    let currencies = client.get_currencies().await.unwrap();
+    let cached = client.get_currencies().await.unwrap();
+    assert_eq!(currencies.len(), cached.len());
+    client.clear_cache();
+    let _products = client.get_products().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_product_candles_range() {
+    let client = PublicClient::new_sandbox();
+    let end = chrono::Utc::now();
+    let candles = client
+        .get_product_candles_range(
+            "BTC-USD",
+            end - chrono::Duration::hours(12),
+            end,
+            Granularity::OneMinute,
+        )
+        .await
+        .unwrap();
+    assert!(candles.windows(2).all(|n| n[0].time < n[1].time));
+}
+
+#[tokio::test]
+async fn test_get_product_candles_range_end_before_start() {
+    let client = PublicClient::new_sandbox();
+    let start = chrono::Utc::now();
+    let candles = client
+        .get_product_candles_range(
+            "BTC-USD",
+            start,
+            start - chrono::Duration::days(1),
+            Granularity::OneDay,
+        )
+        .await;
+    assert!(matches!(
+        candles.unwrap_err().kind,
+        coinbase_client::error::ErrorKind::InvalidParameter(_)
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_daily_volume() {
+    let client = PublicClient::new_sandbox();
+    let volume = client.get_daily_volume("BTC-USD", 400).await.unwrap();
+    assert!(volume >= 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_daily_volume_no_days() {
+    let client = PublicClient::new_sandbox();
+    let volume = client.get_daily_volume("BTC-USD", 0).await.unwrap();
+    assert_eq!(volume, 0.0);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_product_24hr_stats() {
     let client = PublicClient::new_sandbox();
     let _twenty_four_hour_stats = client.get_product_24hr_stats("BTC-USD").await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_get_stats() {
+    let client = PublicClient::new_sandbox();
+    let stats = client.get_stats(&["BTC-USD", "NOT-A-PRODUCT"]).await;
+    assert!(stats["BTC-USD"].is_ok());
+    assert!(stats["NOT-A-PRODUCT"].is_err());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_currencies() {
     let client = PublicClient::new_sandbox();
@@ -76,8 +209,155 @@ async fn test_get_currency() {
     let _currency = client.get_currency("LINK").await.unwrap();
 }
 
+#[tokio::test]
+async fn test_proxy() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // a proxy which answers a single request itself
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let n = socket.read(&mut request).await.unwrap();
+        let body = r#"{"iso": "2015-01-07T23:47:25.201Z", "epoch": 1420674445.201}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request[..n]).to_string()
+    });
+    let client = PublicClient::builder()
+        .url("http://api.example.com")
+        .proxy(reqwest::Proxy::http(&proxy).unwrap())
+        .build()
+        .unwrap();
+    let _time = client.get_time().await.unwrap();
+    let request = server.await.unwrap();
+    assert!(request.starts_with("GET http://api.example.com/time"));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_get_time() {
     let client = PublicClient::new_sandbox();
     let _time = client.get_time().await.unwrap();
 }
+
+#[tokio::test]
+async fn test_gzip_response() {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // a server which answers a single request with a gzipped time
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let n = socket.read(&mut request).await.unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(br#"{"iso": "2015-01-07T23:47:25.201Z", "epoch": 1420674445.201}"#)
+            .unwrap();
+        let body = encoder.finish().unwrap();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-encoding: gzip\r\ncontent-length: {}\r\n\r\n",
+            body.len()
+        );
+        socket.write_all(head.as_bytes()).await.unwrap();
+        socket.write_all(&body).await.unwrap();
+        String::from_utf8_lossy(&request[..n]).to_lowercase()
+    });
+    let client = PublicClient::builder().url(url).build().unwrap();
+    let time = client.get_time().await.unwrap();
+    assert_eq!(time.epoch, 1420674445.201);
+    let request = server.await.unwrap();
+    assert!(request.contains("accept-encoding: gzip, br"));
+}
+
+#[test]
+fn test_ticker_age() {
+    let mut ticker: Ticker = serde_json::from_str(
+        r#"{
+            "trade_id": 4729088,
+            "price": "333.99",
+            "size": "0.193",
+            "bid": "333.98",
+            "ask": "333.99",
+            "volume": "5957.11914015",
+            "time": "2015-11-14T20:46:03.511254Z"
+        }"#,
+    )
+    .unwrap();
+    assert!(ticker.age() > Duration::from_secs(365 * 24 * 60 * 60));
+    ticker.time = chrono::Utc::now() + chrono::Duration::minutes(1);
+    assert_eq!(ticker.age(), Duration::from_secs(0));
+}
+
+#[test]
+fn test_is_sandbox() {
+    assert!(PublicClient::new_sandbox().is_sandbox());
+    assert!(!PublicClient::new().is_sandbox());
+}
+
+#[test]
+fn test_product_is_tradable() {
+    let mut product: Product = serde_json::from_str(
+        r#"{
+            "id": "BTC-USD",
+            "display_name": "BTC/USD",
+            "base_currency": "BTC",
+            "quote_currency": "USD",
+            "base_increment": "0.00000001",
+            "quote_increment": "0.01000000",
+            "base_min_size": "0.00100000",
+            "base_max_size": "280.00000000",
+            "min_market_funds": "5",
+            "max_market_funds": "1000000",
+            "status": "online",
+            "status_message": "",
+            "cancel_only": false,
+            "limit_only": false,
+            "post_only": false,
+            "trading_disabled": false
+        }"#,
+    )
+    .unwrap();
+    assert!(product.is_tradable());
+    product.post_only = true;
+    assert!(!product.is_tradable());
+    product.post_only = false;
+    product.status = "delisted".to_string();
+    assert!(!product.is_tradable());
+}
+
+#[test]
+fn test_currency_decimals() {
+    let currency: Currency = serde_json::from_str(
+        r#"{
+            "id": "BTC",
+            "name": "Bitcoin",
+            "min_size": "0.00000001",
+            "status": "online",
+            "message": null,
+            "max_precision": "0.00000001",
+            "convertible_to": [],
+            "details": {
+                "type": "crypto",
+                "symbol": "₿",
+                "network_confirmations": 3,
+                "sort_order": 3,
+                "crypto_address_link": "https://live.blockcypher.com/btc/address/{{address}}",
+                "crypto_transaction_link": "https://live.blockcypher.com/btc/tx/{{txId}}",
+                "push_payment_methods": ["crypto"],
+                "min_withdrawal_amount": 0.0001,
+                "max_withdrawal_amount": 2400
+            }
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(currency.decimals(), 8);
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "websocket")]
+
+use coinbase_client::websocket::*;
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_websocket_feed() {
+    let mut feed = WebSocketFeed::connect_sandbox(&["BTC-USD"], &["heartbeat"])
+        .await
+        .unwrap();
+    let message = feed.next().await.unwrap().unwrap();
+    assert_eq!(message["type"], "subscriptions");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_order_book_stream() {
+    let mut order_book = OrderBookStream::connect_sandbox("BTC-USD").await.unwrap();
+    let event = order_book.next().await.unwrap().unwrap();
+    assert_eq!(event, OrderBookEvent::Snapshot);
+    assert_eq!(order_book.book().product_id, "BTC-USD");
+}
+
+// a websocket server which answers each subscription of `connections` with a heartbeat, then closes the connection
+async fn closing_server(connections: u64) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut subscriptions = Vec::new();
+        for sequence in 0..connections {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = tokio_tungstenite::accept_async(socket).await.unwrap();
+            let subscribe = stream.next().await.unwrap().unwrap();
+            subscriptions.push(subscribe.into_text().unwrap());
+            let heartbeat = serde_json::json!({"type": "heartbeat", "sequence": sequence});
+            stream
+                .send(Message::Text(heartbeat.to_string()))
+                .await
+                .unwrap();
+            stream.close(None).await.unwrap();
+        }
+        subscriptions
+    });
+    (url, server)
+}
+
+#[tokio::test]
+async fn test_reconnecting_feed_resubscribes() {
+    let (url, server) = closing_server(2).await;
+    let mut feed = ReconnectingFeed::builder(&["BTC-USD"], &["heartbeat"])
+        .url(url)
+        .backoff(Duration::from_millis(10), Duration::from_millis(100))
+        .connect()
+        .await
+        .unwrap();
+    let heartbeat = |sequence: u64| {
+        FeedMessage::Unknown(serde_json::json!({"type": "heartbeat", "sequence": sequence}))
+    };
+    assert_eq!(feed.next().await.unwrap().unwrap(), heartbeat(0));
+    assert_eq!(
+        feed.next().await.unwrap().unwrap(),
+        FeedMessage::Reconnected
+    );
+    assert_eq!(feed.next().await.unwrap().unwrap(), heartbeat(1));
+    let subscriptions = server.await.unwrap();
+    assert_eq!(subscriptions[0], subscriptions[1]);
+    assert!(subscriptions[0].contains("heartbeat"));
+}
+
+#[tokio::test]
+async fn test_reconnecting_feed_max_retries() {
+    // the listener is dropped after the first connection, so reconnecting fails
+    let (url, server) = closing_server(1).await;
+    let mut feed = ReconnectingFeed::builder(&["BTC-USD"], &["heartbeat"])
+        .url(url)
+        .backoff(Duration::from_millis(10), Duration::from_millis(100))
+        .max_retries(2)
+        .connect()
+        .await
+        .unwrap();
+    server.await.unwrap();
+    assert!(feed.next().await.unwrap().is_ok());
+    assert!(feed.next().await.unwrap().is_err());
+    assert!(feed.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_reconnecting_feed_heartbeat_timeout() {
+    // a websocket server which sends a heartbeat on each connection, then goes silent without closing it
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut connections = Vec::new();
+        for sequence in 0..2 {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = tokio_tungstenite::accept_async(socket).await.unwrap();
+            stream.next().await.unwrap().unwrap();
+            let heartbeat = serde_json::json!({"type": "heartbeat", "sequence": sequence});
+            stream
+                .send(Message::Text(heartbeat.to_string()))
+                .await
+                .unwrap();
+            connections.push(stream);
+        }
+        connections
+    });
+    let mut feed = ReconnectingFeed::builder(&["BTC-USD"], &["heartbeat"])
+        .url(url)
+        .backoff(Duration::from_millis(10), Duration::from_millis(100))
+        .heartbeat_timeout(Duration::from_millis(100))
+        .connect()
+        .await
+        .unwrap();
+    assert!(feed.last_heartbeat().is_none());
+    assert!(feed.next().await.unwrap().is_ok());
+    let first_heartbeat = feed.last_heartbeat().unwrap();
+    assert_eq!(
+        feed.next().await.unwrap().unwrap(),
+        FeedMessage::Reconnected
+    );
+    assert!(feed.next().await.unwrap().is_ok());
+    assert!(feed.last_heartbeat().unwrap() > first_heartbeat);
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_reconnecting_feed_authenticates() {
+    let client = coinbase_client::private_client::PrivateClient::new(
+        "tGJSu7SuV3/HOR1/9DcFwO1s560BKI51SDEbnwuvTPbw4BbG5lYJLuKUFpD8TPU61R85dxJpGTygKZ5v+6wJdA==",
+        "t9riylyad0r",
+        "4a9f6de8bcdee641a0a207613dfb43ef",
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut stream = tokio_tungstenite::accept_async(socket).await.unwrap();
+        let subscribe = stream.next().await.unwrap().unwrap();
+        let done = serde_json::json!({
+            "type": "done",
+            "time": "2014-11-07T08:19:27.028459Z",
+            "product_id": "BTC-USD",
+            "sequence": 10,
+            "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+            "reason": "canceled",
+            "side": "buy",
+            "price": "200.2",
+            "remaining_size": "1.0",
+            "user_id": "5844eceecf7e803e259d0365",
+            "profile_id": "765d1549-9660-4be2-97d4-fa2d65fa3352"
+        });
+        stream.send(Message::Text(done.to_string())).await.unwrap();
+        let subscribe: serde_json::Value =
+            serde_json::from_str(&subscribe.into_text().unwrap()).unwrap();
+        subscribe
+    });
+    let mut feed = ReconnectingFeed::builder(&["BTC-USD"], &["user"])
+        .url(url)
+        .authenticate(&client)
+        .connect()
+        .await
+        .unwrap();
+    match feed.next().await.unwrap().unwrap() {
+        FeedMessage::Done {
+            reason, order_id, ..
+        } => {
+            assert_eq!(reason, "canceled");
+            assert_eq!(order_id, "d50ec984-77a8-460a-b958-66f114b0de9b");
+        }
+        other => panic!("expected a done message, got {:?}", other),
+    }
+    let subscribe = server.await.unwrap();
+    assert_eq!(subscribe["key"], "4a9f6de8bcdee641a0a207613dfb43ef");
+    assert_eq!(subscribe["passphrase"], "t9riylyad0r");
+    let prehash = format!(
+        "{}GET/users/self/verify",
+        subscribe["timestamp"].as_str().unwrap()
+    );
+    assert_eq!(
+        subscribe["signature"],
+        client.sign_message_bytes(prehash.as_bytes()).unwrap()
+    );
+}